@@ -0,0 +1,99 @@
+//! diagnostics through the [log](https://docs.rs/log) facade
+//!
+//! with the `log` feature the macros of this module forward to the log crate, so embedders capture game events with any logger.
+//! Without it they compile to nothing, while still type checking their arguments
+
+#[cfg(feature = "log")]
+macro_rules! log_debug {
+	($($arg:tt)+) => {
+		::log::debug!($($arg)+)
+	};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_debug {
+	($($arg:tt)+) => {
+		if false {
+			let _ = format_args!($($arg)+);
+		}
+	};
+}
+
+#[cfg(feature = "log")]
+macro_rules! log_warn {
+	($($arg:tt)+) => {
+		::log::warn!($($arg)+)
+	};
+}
+
+#[cfg(not(feature = "log"))]
+macro_rules! log_warn {
+	($($arg:tt)+) => {
+		if false {
+			let _ = format_args!($($arg)+);
+		}
+	};
+}
+
+pub(crate) use {log_debug, log_warn};
+
+#[cfg(all(test, feature = "log"))]
+mod tests {
+	use std::cell::RefCell;
+	use std::sync::Once;
+
+	use log::{Level, LevelFilter, Log, Metadata, Record};
+
+	use crate::gameboard::{BaseGameBoard, GameBoard, KeyEvent};
+	use crate::logged::LoggedGameBoard;
+
+	thread_local! {
+		/// the records logged on this thread, so tests running in parallel do not see each others records
+		static RECORDS: RefCell<Vec<(Level, String)>> = const { RefCell::new(Vec::new()) };
+	}
+
+	struct Capture;
+
+	impl Log for Capture {
+		fn enabled(&self, _: &Metadata<'_>) -> bool {
+			true
+		}
+
+		fn log(&self, record: &Record<'_>) {
+			RECORDS.with_borrow_mut(|r| r.push((record.level(), record.args().to_string())));
+		}
+
+		fn flush(&self) {}
+	}
+
+	/// installs the capturing logger once per test binary and returns the records logged on this thread by f
+	fn captured(f: impl FnOnce()) -> Vec<(Level, String)> {
+		static INSTALL: Once = Once::new();
+
+		INSTALL.call_once(|| {
+			log::set_logger(&Capture).unwrap();
+			log::set_max_level(LevelFilter::Trace);
+		});
+
+		RECORDS.with_borrow_mut(Vec::clear);
+		f();
+		RECORDS.with_borrow_mut(std::mem::take)
+	}
+
+	#[test]
+	fn invalid_moves_are_logged_as_warnings() {
+		let mut gb = LoggedGameBoard::<GameBoard>::start_new(9, 9, 10, 4, 4).unwrap();
+
+		let records = captured(|| {
+			assert!(gb.do_event(KeyEvent::Mouse1(9, 0)).is_err());
+		});
+
+		assert_eq!(records.len(), 1);
+		assert_eq!(records[0].0, Level::Warn);
+		assert!(records[0].1.starts_with("invalid move Mouse1(9, 0)"));
+
+		// valid moves do not warn
+		let records = captured(|| gb.do_event(KeyEvent::Pause).unwrap());
+		assert!(records.iter().all(|&(level, _)| level != Level::Warn));
+	}
+}
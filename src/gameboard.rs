@@ -1,36 +1,101 @@
 //! main game logic for a minesweeper game
 //!
-//! this module defines the [GameBoard], the core implementation of [BaseGameBoard], that has basic minesweeper logic implemented
+//! this module defines the [`GameBoard`], the core implementation of [`BaseGameBoard`], that has basic minesweeper logic implemented
 
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
 use std::iter::repeat;
 
 use rand::prelude::*;
 
+use crate::diag::{log_debug, log_warn};
+use crate::solver::{autosolve, solve_without_guessing};
+
 mod tiles;
 use tiles::{BoardTile, Visibility};
 
-pub use tiles::{Tile, VisibleTile};
+pub use tiles::{Glyphs, MineCount, Tile, VisibilityMask, VisibleTile};
 
 mod errors;
-use errors::assert_not_bomb;
-pub use errors::{NewBoardError, UndoError, UnopenableError};
+pub use errors::{
+	BoardFormatError, DecodeError, DiffError, NewBoardError, RestoreError, UndoError,
+	UnopenableError,
+};
 
 mod flatboard;
 pub use flatboard::{FlatBoard, IterBacking, IterBackingMut};
 
+mod options;
+pub use options::{BoardOptions, ChordPolicy, Difficulty, RoundMode, WinCondition};
+
 mod interface;
-pub use interface::{BaseGameBoard, BaseGameBoard_do_event, GameBoardEvent, KeyEvent};
+pub(crate) use interface::narrow_xy;
+pub use interface::{
+	bounding_box, diff, tiles_around, BaseGameBoard, BaseGameBoard_do_event, BoardSnapshot,
+	EventOutcome, GameBoardEvent, GameState, KeyEvent, TileCensus,
+};
+
+/// the largest x or y dimension that [`GameBoard::validate_board`] accepts
+///
+/// coordinates are [`u16`] throughout the api, so this is not a limit of the coordinate type but of memory:
+/// a [`GameBoard`] stores 2 bytes per tile, generation allocates another byte per tile, and every [`BaseGameBoard::render`] allocates a byte per tile on top of that.
+/// A board at this limit on both axes already costs several hundred megabytes, boards beyond it are played on a [`SparseGameBoard`][crate::sparse::SparseGameBoard] with u32 coordinates instead.
+pub const MAX_DIMENSION: u16 = 10_000;
+
+/// the largest bomb count that [`GameBoard::validate_board`] accepts
+pub const MAX_BOMBS: u32 = 100_000_000;
 
-#[derive(Debug)]
+/// the amount of boards that [`GameBoard::new_solvable`] generates before giving up
+pub const MAX_SOLVABLE_ATTEMPTS: u32 = 10_000;
+
+/// the amount of boards that [`GameBoard::new_with_3bv_range`] generates before giving up
+pub const MAX_3BV_ATTEMPTS: u32 = 10_000;
+
+/// the amount of boards that [`GameBoard::with_clearing_min_opening`] generates before giving up
+pub const MAX_OPENING_ATTEMPTS: u32 = 10_000;
+
+#[derive(Debug, Clone)]
 pub struct GameBoard {
 	bombs: u32,
 	flagged_tiles: u32,
 	opened_tiles: u32,
 	lost: bool,
+	/// x/y of the bomb that was hit, if any
+	detonated: Option<(u16, u16)>,
+	options: BoardOptions,
+	/// the seed the layout was generated from, if it was seeded
+	seed: Option<u64>,
+	/// the x/y the layout was generated to be clear around, if any
+	clearing: Option<(u16, u16)>,
+	/// player marks that have no effect on play, indexed as y/x like board
+	annotations: FlatBoard<Option<char>>,
 	// board is indexed as y/x but the api uses x/y
 	board: FlatBoard<BoardTile>,
 }
 
+/// the tiles revealed by [`GameBoard::finalize_loss`], as x/y coordinates in row major order
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct LossReveal {
+	/// the bomb that ended the game, or [`None`] if the game was lost without hitting a bomb
+	pub detonated: Option<(u16, u16)>,
+	/// every other bomb, which are now visible unless they were flagged
+	pub bombs: Vec<(u16, u16)>,
+	/// every flag that was not on a bomb
+	pub wrong_flags: Vec<(u16, u16)>,
+}
+
+/// a summary of how hard a board is, returned by [`GameBoard::difficulty_estimate`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyMetrics {
+	/// bombs per tile, from [`BaseGameBoard::bomb_density`]
+	pub density: f64,
+	/// the 3BV of the layout, from [`GameBoard::board_3bv`]
+	pub bv3: u32,
+	/// how many times the solver had to guess to finish the board from its current state
+	pub guess_points: u32,
+}
+
 #[inline]
 fn widening_mul(a: u16, b: u16) -> u32 {
 	u32::from(a) * u32::from(b)
@@ -44,31 +109,14 @@ fn widen_xy<T: From<S>, S>(x: S, y: S) -> (T, T) {
 impl GameBoard {
 	/// builds every point that is accessible in a 3x3 grid around a specified point
 	fn normalize_around_3x3(&self, orig_x: u16, orig_y: u16) -> Vec<(usize, usize)> {
-		// SAFETY: Converted back to usize before use, only used as indexing so negatives will overflow
-		let startx = (orig_x as isize) - 1;
-		let endx = (orig_x as isize) + 1;
-		let starty = (orig_y as isize) - 1;
-		let endy = (orig_y as isize) + 1;
-
-		let mut arr = Vec::with_capacity(8);
-
-		for y in (starty..=endy).map(|i| i as usize) {
-			for x in (startx..=endx).map(|i| i as usize) {
-				if (|| self.board.get(y)?.get(x))().is_some() {
-					// disallow origin being selected
-					if !((usize::from(orig_x) == x) && (usize::from(orig_y) == y)) {
-						arr.push((x, y))
-					}
-				}
-			}
-		}
-
-		arr
+		tiles_around(orig_x, orig_y, self.dimensions())
+			.map(|(x, y)| widen_xy(x, y))
+			.collect()
 	}
 
 	/// validates that bomb counts and size counts do not exceed hard coded limits for sanity
 	const fn validate_size_constraints(x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
-		if x > 10_000 || y > 10_000 || bombs > 100_000_000 {
+		if x > MAX_DIMENSION || y > MAX_DIMENSION || bombs > MAX_BOMBS {
 			Err(NewBoardError::SizeConstraintOverflow)
 		} else {
 			Ok(())
@@ -94,14 +142,15 @@ impl GameBoard {
 // game logic
 impl GameBoard {
 	/// computes the amount of bombs around a tile by reading all 8 tiles around a tile
-	fn computed_bombs_around_tile(&self, unsigned_x: u16, unsigned_y: u16) -> u8 {
+	fn computed_bombs_around_tile(&self, unsigned_x: u16, unsigned_y: u16) -> MineCount {
 		let readable = self.normalize_around_3x3(unsigned_x, unsigned_y);
 
-		let mut bombcount = 0u8;
+		let mut bombcount = MineCount::ZERO;
 
-		for (xoff, yoff) in readable.into_iter() {
+		// there are at most 8 tiles around a tile, so this never saturates
+		for (xoff, yoff) in readable {
 			if self.board[yoff][xoff].tile.is_bomb() {
-				bombcount += 1
+				bombcount = bombcount.saturating_add(1);
 			}
 		}
 
@@ -109,7 +158,7 @@ impl GameBoard {
 	}
 
 	/// implants a vector of bombs into the board
-	fn _populate_implant(&mut self, arr: Vec<bool>) {
+	fn populate_implant(&mut self, arr: Vec<bool>) {
 		let mut it = arr.into_iter();
 
 		for t in self.board.iter_backing_mut() {
@@ -124,59 +173,87 @@ impl GameBoard {
 		for y in 0..self.board.len() {
 			for x in 0..self.board[y].len() {
 				if !(self.board[y][x].tile == Tile::Bomb) {
-					self.board[y][x].tile = self
-						.computed_bombs_around_tile(x as u16, y as u16)
-						.try_into()
-						.expect("More than 8 bombs surrounding tile (impossible invariant)");
+					let (ux, uy) = narrow_xy(x, y);
+					self.board[y][x].tile = self.computed_bombs_around_tile(ux, uy).into();
 				}
 			}
 		}
 	}
 
 	/// populates a minesweeper board with bombs and computes tiles around it
-	fn populate(&mut self) {
-		let mut rng = rand::thread_rng();
-
-		let mut arr: Vec<bool> = repeat(true)
-			.take(self.bombs.try_into().expect("bomb count overflowed usize"))
-			.chain(repeat(false))
-			.take(self.area().try_into().expect("area overflowed usize"))
-			.collect();
+	fn populate(&mut self, rng: &mut impl Rng) {
+		let mut arr: Vec<bool> = std::iter::repeat_n(
+			true,
+			self.bombs.try_into().expect("bomb count overflowed usize"),
+		)
+		.chain(repeat(false))
+		.take(self.area().try_into().expect("area overflowed usize"))
+		.collect();
 
-		arr.shuffle(&mut rng);
-		arr.shuffle(&mut rng);
+		arr.shuffle(rng);
+		arr.shuffle(rng);
 
-		self._populate_implant(arr);
+		self.populate_implant(arr);
 	}
 
-	/// populates a board with bombs without bombs around a certain xy coordinate in a 3x3 grid
-	fn populate_without(&mut self, x: u16, y: u16) -> Result<(), NewBoardError> {
-		let mut valid = self.normalize_around_3x3(x, y);
-		// include self in valid
-		valid.push((x.into(), y.into()));
+	/// populates a board with bombs without bombs in the 3x3 grid around any of the clear x/y coordinates
+	fn populate_without(
+		&mut self,
+		clears: &[(u16, u16)],
+		rng: &mut impl Rng,
+	) -> Result<(), NewBoardError> {
+		let mut reserved = FlatBoard::new(self.board.len(), self.dimensions().0.into(), false);
+		let mut valid = Vec::new();
+
+		for &(x, y) in clears {
+			if self.get(x, y).is_none() {
+				return Err(NewBoardError::SizeConstraintOverflow);
+			}
 
-		if (self.area() - self.bombs) < 9 {
-			return Err(NewBoardError::BombOverflow);
+			// include self in valid
+			let mut zone = self.normalize_around_3x3(x, y);
+			zone.push((x.into(), y.into()));
+
+			// overlapping zones only reserve each tile once
+			for (zx, zy) in zone {
+				if !std::mem::replace(&mut reserved[zy][zx], true) {
+					valid.push((zx, zy));
+				}
+			}
 		}
 
-		let mut rng = rand::thread_rng();
+		self.populate_reserving(&valid, rng)?;
+		self.clearing = clears.first().copied();
+
+		Ok(())
+	}
+
+	/// populates a board with bombs without bombs on any of the valid x/y coordinates, which must be in bounds and unique
+	fn populate_reserving(
+		&mut self,
+		valid: &[(usize, usize)],
+		rng: &mut impl Rng,
+	) -> Result<(), NewBoardError> {
+		if u32::try_from(valid.len()).unwrap() > (self.area() - self.bombs) {
+			return Err(NewBoardError::BombOverflow);
+		}
 
 		// SAFETY: panics are impossible on 64 bit machines due to bombcount and area being u32
 		// 32 bit machines might overflow isize constraints, but at that point there is no memory left
-		let mut arr: Vec<bool> = repeat(true)
-			.take(
-				self.bomb_count()
-					.try_into()
-					.expect("bomb count overflowed usize"),
-			)
-			.chain(repeat(false))
-			.take(self.area().try_into().expect("area overflowed usize"))
-			.collect();
+		let mut arr: Vec<bool> = std::iter::repeat_n(
+			true,
+			self.bomb_count()
+				.try_into()
+				.expect("bomb count overflowed usize"),
+		)
+		.chain(repeat(false))
+		.take(self.area().try_into().expect("area overflowed usize"))
+		.collect();
 
-		arr.shuffle(&mut rng);
+		arr.shuffle(rng);
 
 		// flattens a [y][x] indexed flat array into its true index
-		let flatten = |x, y| ((y * usize::from(self.dimensions().0)) + x);
+		let flatten = |x, y| (y * usize::from(self.dimensions().0)) + x;
 
 		let mut reroute = true;
 
@@ -193,7 +270,8 @@ impl GameBoard {
 			}
 		}
 
-		self._populate_implant(arr);
+		self.populate_implant(arr);
+
 		Ok(())
 	}
 
@@ -205,6 +283,11 @@ impl GameBoard {
 			flagged_tiles: 0,
 			opened_tiles: 0,
 			lost: false,
+			detonated: None,
+			options: BoardOptions::default(),
+			seed: None,
+			clearing: None,
+			annotations: FlatBoard::new(y.into(), x.into(), None),
 			board: FlatBoard::new(
 				y.into(),
 				x.into(),
@@ -219,12 +302,13 @@ impl GameBoard {
 	/// validation method for a new board, able to run publically for validating board configs before more costly generation
 	///
 	/// it is in theory safe to call [`Result::unwrap_unchecked`] on associated new methods if this function does not return Err with the same configuration, however this is not recommended anyways
+	///
+	/// passing clearing coordinates validates a board with a 3x3 clear zone around them, which must be in bounds and leave room for the zone
 	pub fn validate_board(
 		x: u16,
 		y: u16,
 		bombs: u32,
-		has_clearing: bool,
-		clearing_coordinates: impl Into<Option<(u16, u16)>>,
+		clearing: impl Into<Option<(u16, u16)>>,
 	) -> Result<(), NewBoardError> {
 		Self::validate_size_constraints(x, y, bombs)?;
 
@@ -237,249 +321,1491 @@ impl GameBoard {
 			return Err(NewBoardError::ZeroDimension);
 		}
 
-		if let Some((clearx, cleary)) = clearing_coordinates.into() {
+		if let Some((clearx, cleary)) = clearing.into() {
 			if !((clearx < x) && (cleary < y)) {
 				return Err(NewBoardError::SizeConstraintOverflow);
 			}
-		}
 
-		if has_clearing && (area - bombs) < 9 {
-			return Err(NewBoardError::BombOverflow);
+			if (area - bombs) < 9 {
+				return Err(NewBoardError::BombOverflow);
+			}
 		}
 
 		Ok(())
 	}
 
+	/// validates a board like [`GameBoard::validate_board`], also rejecting boards whose tiles would take more than `max_bytes` of memory
+	///
+	/// the size counts only the tiles and not the fixed size of the board itself, returning [`NewBoardError::MemoryBudgetExceeded`] when it is over budget
+	pub fn validate_board_within(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		max_bytes: usize,
+	) -> Result<(), NewBoardError> {
+		Self::validate_board(x, y, bombs, None)?;
+
+		let bytes = usize::try_from(widening_mul(x, y))
+			.ok()
+			.and_then(|area| area.checked_mul(std::mem::size_of::<BoardTile>()));
+
+		match bytes {
+			Some(bytes) if bytes <= max_bytes => Ok(()),
+			_ => Err(NewBoardError::MemoryBudgetExceeded),
+		}
+	}
+
 	/// generates a new board
 	pub fn new(x: u16, y: u16, bombs: u32) -> Result<Self, NewBoardError> {
-		Self::validate_board(x, y, bombs, false, None)?;
+		Self::validate_board(x, y, bombs, None)?;
 		let mut gb = Self::blank_board(x, y, bombs);
 
-		gb.populate();
+		gb.populate(&mut thread_rng());
 
 		Ok(gb)
 	}
 
-	/// opens all visible tiles it sees, appends each coordinate to opened, and returns a final count of the amount of cells opened
-	fn inner_open_visible(&mut self, opened: &mut Vec<(u16, u16)>) -> usize {
-		let mut opened_count = 0usize;
+	/// generates a board with bombs at exactly the given x/y coordinates, which must be in bounds and unique
+	pub fn with_bombs(x: u16, y: u16, bombs: &[(u16, u16)]) -> Result<Self, NewBoardError> {
+		let count = u32::try_from(bombs.len()).map_err(|_| NewBoardError::BombOverflow)?;
 
-		for y in 0..self.board.len() {
-			for x in 0..self.board[y].len() {
-				let tile = self.board[y][x];
-				if tile.visible == Visibility::Visible && tile.tile == Tile::Zero {
-					for (x, y) in self.normalize_around_3x3(x as u16, y as u16) {
-						let (x, y) = (x as u16, y as u16);
-						if self.get(x, y).unwrap().visible == Visibility::NotVisible {
-							opened.push((x, y));
-							opened_count += 1;
-							// SAFETY: all tiles around a tile are not bombs because the current tile is a Zero, so overwrite with a Visible
-							self.get_mut(x, y).unwrap().visible = Visibility::Visible;
-						}
-					}
-				}
+		Self::validate_board(x, y, count, None)?;
+		let mut gb = Self::blank_board(x, y, count);
+
+		let mut arr = vec![
+			false;
+			widening_mul(x, y)
+				.try_into()
+				.expect("area overflowed usize")
+		];
+
+		for &(bx, by) in bombs {
+			if !(bx < x && by < y) {
+				return Err(NewBoardError::BombOutOfBounds);
+			}
+
+			let idx = usize::from(by) * usize::from(x) + usize::from(bx);
+
+			if arr[idx] {
+				return Err(NewBoardError::DuplicateBomb);
 			}
+
+			arr[idx] = true;
 		}
-		opened_count
+
+		gb.populate_implant(arr);
+
+		Ok(gb)
 	}
 
-	/// opens all tiles that are naively open-able and stores in out_arr
-	fn open_visible(&mut self, out_arr: &mut Vec<(u16, u16)>) {
-		let mut per_iter = self.inner_open_visible(out_arr);
+	/// creates a fully hidden board from a layout of tiles, where only the placement of bombs is kept and every number is recomputed
+	///
+	/// this is the lowest level constructor, for tools that build or import layouts themselves
+	pub fn from_flatboard(board: &FlatBoard<Tile>) -> Result<Self, NewBoardError> {
+		let (dim_y, dim_x) = board.dimensions();
 
-		while per_iter != 0 {
-			per_iter = self.inner_open_visible(out_arr);
-		}
+		let x = u16::try_from(dim_x).map_err(|_| NewBoardError::SizeConstraintOverflow)?;
+		let y = u16::try_from(dim_y).map_err(|_| NewBoardError::SizeConstraintOverflow)?;
+
+		// row major backing order is the same order populate_implant expects
+		let arr: Vec<bool> = board.iter_backing().map(|t| t.is_bomb()).collect();
+
+		let count = arr.iter().filter(|&&b| b).count();
+		let count = u32::try_from(count).map_err(|_| NewBoardError::BombOverflow)?;
+
+		Self::validate_board(x, y, count, None)?;
+		let mut gb = Self::blank_board(x, y, count);
+
+		gb.populate_implant(arr);
+
+		Ok(gb)
 	}
 
-	fn assert_not_lost(&self) -> Result<(), UnopenableError> {
-		if self.lost {
-			Err(UnopenableError::GameOver)
-		} else {
-			Ok(())
+	/// generates a new board with a bomb density in the range \[0,1\], rounding to the nearest bomb count
+	pub fn with_density(x: u16, y: u16, density: f64) -> Result<Self, NewBoardError> {
+		Self::with_density_rounding(x, y, density, RoundMode::Nearest)
+	}
+
+	/// generates a new board with a bomb density in the range \[0,1\], rounding the bomb count with mode
+	///
+	/// densities outside of the range, including NaN, return [`NewBoardError::InvalidDensity`]
+	pub fn with_density_rounding(
+		x: u16,
+		y: u16,
+		density: f64,
+		mode: RoundMode,
+	) -> Result<Self, NewBoardError> {
+		// NaN is never contained in a range
+		if !(0.0..=1.0).contains(&density) {
+			return Err(NewBoardError::InvalidDensity);
 		}
+
+		// density is in [0,1] so the product is in [0,area], and float to int casts saturate regardless
+		#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+		let bombs = mode.apply(density * f64::from(widening_mul(x, y))) as u32;
+
+		Self::new(x, y, bombs)
 	}
-}
 
-// core implementation of BaseGameBoard
-impl BaseGameBoard for GameBoard {
-	fn bomb_count(&self) -> u32 {
-		self.bombs
+	/// generates a new board like [`GameBoard::new`], where the same seed and configuration always generate the same layout
+	pub fn new_seeded(x: u16, y: u16, bombs: u32, seed: u64) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, None)?;
+		let mut gb = Self::blank_board(x, y, bombs);
+
+		gb.populate(&mut StdRng::seed_from_u64(seed));
+		gb.seed = Some(seed);
+
+		Ok(gb)
 	}
 
-	fn dimensions(&self) -> (u16, u16) {
-		(
-			self.board.dimensions().1.try_into().unwrap(),
-			self.board.len().try_into().unwrap(),
-		)
+	/// generates the seeded puzzle of a year/month/day date, so everyone playing the same date and difficulty gets the same board
+	///
+	/// the seed derivation is fixed and never depends on the platform, see [`GameBoard::daily_seed`]
+	pub fn daily(date: (i32, u8, u8), difficulty: Difficulty) -> Result<Self, NewBoardError> {
+		let (x, y, bombs) = difficulty.config();
+
+		Self::new_seeded(x, y, bombs, Self::daily_seed(date, difficulty))
 	}
 
-	fn flagged(&self) -> u32 {
-		self.flagged_tiles
+	/// returns the seed [`GameBoard::daily`] generates the board of a date from
+	///
+	/// this packs the date and board configuration into a u64 and mixes it with the splitmix64 finalizer,
+	/// it must not change between releases or every daily puzzle changes with it
+	#[must_use]
+	pub const fn daily_seed(date: (i32, u8, u8), difficulty: Difficulty) -> u64 {
+		const fn mix(mut z: u64) -> u64 {
+			z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+			z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+			z ^ (z >> 31)
+		}
+
+		let (year, month, day) = date;
+		let (x, y, bombs) = difficulty.config();
+
+		// the year is reinterpreted as its u32 bits, which keeps negative years distinct
+		let year = u32::from_le_bytes(year.to_le_bytes());
+		let date = ((year as u64) << 16) | ((month as u64) << 8) | day as u64;
+		let config = ((x as u64) << 48) | ((y as u64) << 32) | bombs as u64;
+
+		mix(mix(date) ^ config)
 	}
 
-	fn opened(&self) -> u32 {
-		self.opened_tiles
+	/// generates a new board like [`BaseGameBoard::with_clearing`], where the same seed and configuration always generate the same layout
+	pub fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, (clearx, cleary))?;
+		let mut gb = Self::blank_board(x, y, bombs);
+
+		gb.populate_without(&[(clearx, cleary)], &mut StdRng::seed_from_u64(seed))?;
+		gb.seed = Some(seed);
+
+		Ok(gb)
 	}
 
-	/// generates a new board with a given clear zone where no bombs will be guaranteed
-	fn with_clearing(
+	/// generates a new board where only the tile at clearx/cleary is guaranteed not to be a bomb, instead of the 3x3 zone of [`BaseGameBoard::with_clearing`]
+	///
+	/// this only reserves a single tile, so boards can be as dense as `area - 1` bombs,
+	/// but opening the clearing may reveal just a number instead of an opening
+	pub fn with_single_safe(
 		x: u16,
 		y: u16,
 		bombs: u32,
 		clearx: u16,
 		cleary: u16,
 	) -> Result<Self, NewBoardError> {
-		Self::validate_board(x, y, bombs, true, (clearx, cleary))?;
+		Self::validate_board(x, y, bombs, None)?;
+
+		if !((clearx < x) && (cleary < y)) {
+			return Err(NewBoardError::SizeConstraintOverflow);
+		}
 
 		let mut gb = Self::blank_board(x, y, bombs);
 
-		gb.populate_without(clearx, cleary)?;
+		gb.populate_reserving(&[widen_xy(clearx, cleary)], &mut thread_rng())?;
+		gb.clearing = Some((clearx, cleary));
 
 		Ok(gb)
 	}
 
-	/// opens the 8 tiles around a tile
-	fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.assert_not_lost()?;
-		let openable = self.normalize_around_3x3(x, y);
+	/// generates a new board with a 3x3 clear zone around each of the clear x/y coordinates, which may overlap
+	///
+	/// every clear coordinate must be in bounds, and the reserved tiles of all zones must leave room for every bomb
+	pub fn with_clearings(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clears: &[(u16, u16)],
+	) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, None)?;
+		let mut gb = Self::blank_board(x, y, bombs);
 
-		let mut opened = Vec::with_capacity(openable.len());
+		gb.populate_without(clears, &mut thread_rng())?;
 
-		let mut bombcnt = 0u32;
+		Ok(gb)
+	}
 
-		for &(x, y) in openable.iter() {
-			let tile = self.board[y][x];
+	/// generates a seeded board whose [`GameBoard::board_3bv`] is within `min_3bv..=max_3bv`
+	///
+	/// each attempt is generated with [`GameBoard::new_seeded`] from a seed derived from seed, so the returned board reports the seed that reproduces it.
+	/// Ranges far from the typical 3BV of a configuration are rarely hit, and may take all [`MAX_3BV_ATTEMPTS`] attempts before returning [`NewBoardError::AttemptsExhausted`]
+	pub fn new_with_3bv_range(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		min_3bv: u32,
+		max_3bv: u32,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, None)?;
 
-			if tile.visible == Visibility::Flagged {
-				bombcnt += 1;
+		let mut seeds = StdRng::seed_from_u64(seed);
+
+		for attempt in 1..=MAX_3BV_ATTEMPTS {
+			let gb = Self::new_seeded(x, y, bombs, seeds.gen())?;
+
+			if (min_3bv..=max_3bv).contains(&gb.board_3bv()) {
+				log_debug!(
+					"generated a board with 3BV in {min_3bv}..={max_3bv} after {attempt} attempts"
+				);
+				return Ok(gb);
 			}
 		}
 
-		if bombcnt
-			!= (self
-				.tile_or_unopenable(x, y)?
-				.tile
-				.as_count()
-				.ok_or(UnopenableError::BombHit)? as u32)
-		{
-			return Err(UnopenableError::FlagCountMismatch);
-		}
+		log_warn!("no board with 3BV in {min_3bv}..={max_3bv} within {MAX_3BV_ATTEMPTS} attempts");
+		Err(NewBoardError::AttemptsExhausted)
+	}
 
-		for &(x, y) in openable.iter() {
-			let tile = self.board[y][x];
+	/// generates a seeded board with a clearing like [`GameBoard::with_clearing_seeded`], where opening the clearing reveals at least `min_opening` tiles
+	///
+	/// each attempt is generated from a seed derived from seed, so the returned board reports the seed that reproduces it.
+	/// Returns [`NewBoardError::AttemptsExhausted`] if no board within [`MAX_OPENING_ATTEMPTS`] attempts has a large enough opening
+	pub fn with_clearing_min_opening(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		min_opening: u32,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, (clearx, cleary))?;
 
-			match tile.visible {
-				// ignore visible tiles
-				// don't attempt to open flagged tiles
-				Visibility::Visible | Visibility::Flagged => (),
-				Visibility::NotVisible => {
-					// if the notvisible tile we are trying to open is a bomb raise error
-					assert_not_bomb(tile.tile)?;
+		let mut seeds = StdRng::seed_from_u64(seed);
 
-					self.board[y][x].visible = Visibility::Visible;
-					opened.push((x as u16, y as u16));
-				}
+		for attempt in 1..=MAX_OPENING_ATTEMPTS {
+			let gb = Self::with_clearing_seeded(x, y, bombs, clearx, cleary, seeds.gen())?;
+
+			let opening: usize = gb
+				.connected_zero_region(clearx, cleary)
+				.iter()
+				.map(Vec::len)
+				.sum();
+
+			if u32::try_from(opening).unwrap() >= min_opening {
+				log_debug!(
+					"generated a board with an opening of {opening} after {attempt} attempts"
+				);
+				return Ok(gb);
 			}
 		}
 
-		// open visible tiles to complete cycle
-		self.open_visible(&mut opened);
+		log_warn!(
+			"no board with an opening of {min_opening} within {MAX_OPENING_ATTEMPTS} attempts"
+		);
+		Err(NewBoardError::AttemptsExhausted)
+	}
 
-		self.opened_tiles += u32::try_from(opened.len()).unwrap();
+	/// generates a new seeded board of the given dimensions, with the bomb count scaled to keep the [`BaseGameBoard::bomb_density`] of this board
+	///
+	/// the bomb count is rounded to the nearest integer and clamped to the area and [`MAX_BOMBS`]
+	pub fn resize_preserving_density(
+		&self,
+		new_x: u16,
+		new_y: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		let area = widening_mul(new_x, new_y);
 
-		Ok(opened.into())
+		// density is in [0,1] so the product is in [0,area], and float to int casts saturate regardless
+		#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+		let bombs = (RoundMode::Nearest.apply(self.bomb_density() * f64::from(area)) as u32)
+			.min(area)
+			.min(MAX_BOMBS);
+
+		Self::new_seeded(new_x, new_y, bombs, seed)
 	}
 
-	/// opens the given tile
-	fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.assert_not_lost()?;
-		let tile = self.tile_or_unopenable(x, y)?;
-		let (x, y) = widen_xy(x, y);
+	/// returns the seed this board was generated from, or [`None`] if it was generated from thread local randomness
+	#[must_use]
+	pub const fn seed(&self) -> Option<u64> {
+		self.seed
+	}
 
-		match tile.visible {
-			Visibility::Visible => Err(UnopenableError::AlreadyOpen),
-			Visibility::Flagged => Err(UnopenableError::FlaggedTile),
-			Visibility::NotVisible => Ok(()),
-		}?;
+	/// returns an unplayed copy of this board with the same hidden layout, seed, clearing and options, for retrying the exact board after a loss
+	///
+	/// the layout is copied instead of regenerated from the seed, so this also works on boards that were not generated from a seed
+	#[must_use]
+	pub fn restart_same_seed(&self) -> Self {
+		let mut gb = self.clone();
 
-		if tile.tile.is_bomb() {
-			return Err(UnopenableError::BombHit);
+		for tile in gb.board.iter_backing_mut() {
+			tile.visible = Visibility::NotVisible;
 		}
 
-		// already confirmed bounds using get(y).get(x)
-		self.board[y][x].visible = Visibility::Visible;
+		gb.flagged_tiles = 0;
+		gb.opened_tiles = 0;
+		gb.lost = false;
+		gb.detonated = None;
+		gb.clear_annotations();
 
-		let mut opened = Vec::new();
-		self.open_visible(&mut opened);
-		// include own tile
-		opened.push((x as u16, y as u16));
+		gb
+	}
 
-		self.opened_tiles += u32::try_from(opened.len()).unwrap();
+	/// returns the x/y the layout was generated to be clear around, which is the first clearing for boards with several,
+	/// or [`None`] if the board was generated without a clearing
+	#[must_use]
+	pub const fn clearing(&self) -> Option<(u16, u16)> {
+		self.clearing
+	}
 
-		Ok(opened.into())
+	/// generates a board with a clearing that the solver can win from the clearing without ever guessing
+	pub fn new_solvable(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+	) -> Result<Self, NewBoardError> {
+		Self::new_solvable_counted(x, y, bombs, clearx, cleary).map(|(gb, _)| gb)
 	}
 
-	/// flags or unflags a tile depending on whether it is flagged already
-	/// errors on an already open tile
-	fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.assert_not_lost()?;
-		let tile = self.tile_or_unopenable(x, y)?;
-		let (bx, by) = widen_xy(x, y);
+	/// like [`GameBoard::new_solvable`], but also returns how many boards were generated and solved to find one
+	///
+	/// solvable boards get rare at high densities, so this returns [`NewBoardError::AttemptsExhausted`] after [`MAX_SOLVABLE_ATTEMPTS`] boards
+	pub fn new_solvable_counted(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+	) -> Result<(Self, u32), NewBoardError> {
+		Self::validate_board(x, y, bombs, (clearx, cleary))?;
 
-		match tile.visible {
-			Visibility::Visible => Err(UnopenableError::AlreadyOpen),
-			Visibility::Flagged => {
-				self.board[by][bx].visible = Visibility::NotVisible;
-				self.flagged_tiles -= 1;
-				Ok(GameBoardEvent::flag_tile(x, y))
-			}
-			Visibility::NotVisible => {
-				self.board[by][bx].visible = Visibility::Flagged;
-				self.flagged_tiles += 1;
-				Ok(GameBoardEvent::flag_tile(x, y))
+		for attempt in 1..=MAX_SOLVABLE_ATTEMPTS {
+			let gb = Self::with_clearing(x, y, bombs, clearx, cleary)?;
+
+			let mut trial = gb.clone();
+
+			if trial.open_tile(clearx, cleary).is_ok() && solve_without_guessing(&mut trial) {
+				log_debug!("generated a solvable board after {attempt} attempts");
+				return Ok((gb, attempt));
 			}
 		}
-	}
 
-	/// gets a specific tile on the board for public inspection
-	fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
-		let (x, y) = widen_xy(x, y);
+		log_warn!("no solvable board within {MAX_SOLVABLE_ATTEMPTS} attempts");
+		Err(NewBoardError::AttemptsExhausted)
+	}
 
-		let tile = self.board.get(y)?.get(x)?;
-		Some(match tile.visible {
-			Visibility::Visible => VisibleTile::Visible(tile.tile),
-			Visibility::NotVisible => VisibleTile::NotVisible,
-			Visibility::Flagged => VisibleTile::Flagged,
-		})
+	/// returns true if the solver can finish the game from the current visible state without a single guess,
+	/// and false once every remaining move is a guess, such as a 50/50
+	///
+	/// the solver plays a copy of the board and only opens tiles it proved safe, so nothing is learned that deduction could not find
+	#[must_use]
+	pub fn is_solvable_from_here(&self) -> bool {
+		self.state() != GameState::Lost && solve_without_guessing(&mut self.clone())
 	}
 
-	/// undoes a move specified by a gameboard event
-	fn undo_move(&mut self, event: &GameBoardEvent) -> Result<(), UndoError> {
-		match event {
-			&GameBoardEvent::ToggleFlagCell(x, y) => {
-				self.get_mut(x, y)
-					.ok_or(UndoError::OutOfBounds)?
-					.swap_flag()
-					.or(Err(UndoError::AlreadyOpen))?;
-				if self.get(x, y).unwrap().visible == Visibility::NotVisible {
-					self.flagged_tiles -= 1;
-				} else {
-					self.flagged_tiles += 1;
+	/// opens all visible tiles it sees, appends each coordinate to opened, and returns a final count of the amount of cells opened
+	fn inner_open_visible(&mut self, opened: &mut Vec<(u16, u16)>) -> usize {
+		let mut opened_count = 0usize;
+
+		for y in 0..self.board.len() {
+			for x in 0..self.board[y].len() {
+				let tile = self.board[y][x];
+				if tile.visible == Visibility::Visible && tile.tile == Tile::Zero {
+					let (ux, uy) = narrow_xy(x, y);
+
+					for (x, y) in self.normalize_around_3x3(ux, uy) {
+						let (x, y) = narrow_xy(x, y);
+						if self.get(x, y).unwrap().visible == Visibility::NotVisible {
+							opened.push((x, y));
+							opened_count += 1;
+							// SAFETY: all tiles around a tile are not bombs because the current tile is a Zero, so overwrite with a Visible
+							self.get_mut(x, y).unwrap().visible = Visibility::Visible;
+						}
+					}
+				}
+			}
+		}
+		opened_count
+	}
+
+	/// opens all tiles that are naively open-able and stores in `out_arr`
+	fn open_visible(&mut self, out_arr: &mut Vec<(u16, u16)>) {
+		let mut per_iter = self.inner_open_visible(out_arr);
+
+		while per_iter != 0 {
+			per_iter = self.inner_open_visible(out_arr);
+		}
+	}
+
+	/// returns the x/y of the bomb that ended the game, once the game has been lost
+	const fn shown_detonation(&self) -> Option<(u16, u16)> {
+		if self.lost {
+			self.detonated
+		} else {
+			None
+		}
+	}
+
+	/// maps a stored tile to what the player can see of it, revealing wrong flags once the game is lost
+	const fn visible_tile(&self, tile: BoardTile) -> VisibleTile {
+		match tile.visible {
+			Visibility::Visible => VisibleTile::Visible(tile.tile),
+			Visibility::NotVisible => VisibleTile::NotVisible,
+			Visibility::Flagged if self.lost && !tile.tile.is_bomb() => VisibleTile::WrongFlag,
+			Visibility::Flagged => VisibleTile::Flagged,
+		}
+	}
+
+	/// asserts that x/y can be opened by [`BaseGameBoard::open_tile`], recording the detonation if it is a bomb
+	fn assert_openable(&mut self, x: u16, y: u16) -> Result<(), UnopenableError> {
+		self.assert_playing()?;
+		let tile = self.tile_or_unopenable(x, y)?;
+
+		match tile.visible {
+			Visibility::Visible => Err(UnopenableError::AlreadyOpen),
+			Visibility::Flagged => Err(UnopenableError::FlaggedTile),
+			Visibility::NotVisible => Ok(()),
+		}?;
+
+		if tile.tile.is_bomb() {
+			return Err(self.detonate(x, y));
+		}
+
+		Ok(())
+	}
+
+	/// records the bomb at x/y as the one that ended the game, loses the game, and returns the [`UnopenableError::BombHit`] to report
+	fn detonate(&mut self, x: u16, y: u16) -> UnopenableError {
+		self.detonated = Some((x, y));
+		self.lose_game();
+
+		UnopenableError::BombHit(x, y)
+	}
+
+	/// returns the tiles that opening the unopened non bomb tile at x/y would reveal,
+	/// grouped by their distance from x/y in the order a flood fill reaches them
+	fn connected_zero_region(&self, x: u16, y: u16) -> Vec<Vec<(u16, u16)>> {
+		let (dim_y, dim_x) = self.board.dimensions();
+		let mut seen = FlatBoard::new(dim_y, dim_x, false);
+		seen[y.into()][usize::from(x)] = true;
+
+		let mut rings = vec![vec![(x, y)]];
+
+		loop {
+			let mut ring = Vec::new();
+
+			// only zeros spread the flood fill, and all tiles around a zero are not bombs
+			for &(x, y) in rings.last().unwrap() {
+				if self.get(x, y).unwrap().tile != Tile::Zero {
+					continue;
+				}
+
+				for (nx, ny) in self.normalize_around_3x3(x, y) {
+					if !seen[ny][nx] && self.board[ny][nx].visible == Visibility::NotVisible {
+						seen[ny][nx] = true;
+						ring.push(narrow_xy(nx, ny));
+					}
+				}
+			}
+
+			if ring.is_empty() {
+				return rings;
+			}
+
+			rings.push(ring);
+		}
+	}
+
+	/// returns how many tiles opening x/y would reveal, which is 0 for bombs and tiles that can not be opened
+	///
+	/// **this peeks at the hidden layout**, it exists for scoring candidate clearings during generation
+	pub fn opening_size_at(&self, x: u16, y: u16) -> u32 {
+		match self.get(x, y) {
+			Some(tile) if tile.visible == Visibility::NotVisible && !tile.tile.is_bomb() => (),
+			_ => return 0,
+		}
+
+		if !self.options.auto_cascade {
+			return 1;
+		}
+
+		let size: usize = self.connected_zero_region(x, y).iter().map(Vec::len).sum();
+
+		u32::try_from(size).unwrap()
+	}
+
+	/// opens the given tile like [`BaseGameBoard::open_tile`], but returns the opened tiles grouped by their distance from x/y in breadth first order
+	///
+	/// the first group is always x/y itself, and flattening the groups into a [`GameBoardEvent`] allows undoing the move
+	pub fn open_tile_bfs(
+		&mut self,
+		x: u16,
+		y: u16,
+	) -> Result<Vec<Vec<(u16, u16)>>, UnopenableError> {
+		self.assert_openable(x, y)?;
+
+		let rings = if self.options.auto_cascade {
+			self.connected_zero_region(x, y)
+		} else {
+			vec![vec![(x, y)]]
+		};
+
+		for &(x, y) in rings.iter().flatten() {
+			self.get_mut(x, y).unwrap().visible = Visibility::Visible;
+		}
+
+		self.opened_tiles += u32::try_from(rings.iter().map(Vec::len).sum::<usize>()).unwrap();
+
+		Ok(rings)
+	}
+
+	/// opens exactly the given tiles without cascading, as a single event, for setting up a known position
+	///
+	/// this is all or nothing, nothing is opened if any tile is out of bounds, flagged or a bomb,
+	/// and a bomb in the set returns [`UnopenableError::RegionContainsBomb`] without losing the game.
+	/// Tiles that are already open or repeated are skipped and not part of the event
+	pub fn open_region(&mut self, cells: &[(u16, u16)]) -> Result<GameBoardEvent, UnopenableError> {
+		self.assert_playing()?;
+
+		for &(x, y) in cells {
+			let tile = self.tile_or_unopenable(x, y)?;
+
+			if tile.visible == Visibility::Flagged {
+				return Err(UnopenableError::FlaggedTile);
+			}
+
+			if tile.tile.is_bomb() {
+				return Err(UnopenableError::RegionContainsBomb(x, y));
+			}
+		}
+
+		let mut opened = Vec::with_capacity(cells.len());
+
+		for &(x, y) in cells {
+			let tile = self.get_mut(x, y).unwrap();
+
+			if tile.visible == Visibility::NotVisible {
+				tile.visible = Visibility::Visible;
+				opened.push((x, y));
+			}
+		}
+
+		self.opened_tiles += u32::try_from(opened.len()).unwrap();
+
+		Ok(opened.into())
+	}
+
+	/// opens every remaining safe tile as a single event, if the solver can prove all of them safe from the visible state of the board
+	///
+	/// this never guesses, and flags are trusted like the solver does, so a wrong flag returns [`UnopenableError::NotDeducible`] without changing the board.
+	/// Opening every safe tile wins under [`WinCondition::OpenAllSafe`], flags are not placed for the other win conditions
+	pub fn try_finish(&mut self) -> Result<GameBoardEvent, UnopenableError> {
+		self.assert_playing()?;
+
+		let mut solved = self.clone();
+		// the solver flags every bomb it deduces, which a flag limit could refuse,
+		// and flagging every bomb would end a flag win condition before every safe tile is opened
+		solved.options.limit_flags = false;
+		solved.options.win_condition = WinCondition::OpenAllSafe;
+
+		if !solve_without_guessing(&mut solved) {
+			return Err(UnopenableError::NotDeducible);
+		}
+
+		let safe: Vec<(u16, u16)> = self
+			.cells()
+			.filter(|&(xy, tile)| {
+				tile == VisibleTile::NotVisible
+					&& matches!(
+						solved.get_board_tile(xy.0, xy.1),
+						Some(VisibleTile::Visible(_))
+					)
+			})
+			.map(|(xy, _)| xy)
+			.collect();
+
+		self.open_region(&safe)
+	}
+
+	/// returns how many moves are still needed to meet the win condition of this board
+	fn left_to_win(&self) -> u32 {
+		let flags_left = || {
+			let correct = u32::try_from(
+				self.board
+					.iter_backing()
+					.filter(|t| t.visible == Visibility::Flagged && t.tile.is_bomb())
+					.count(),
+			)
+			.unwrap();
+
+			(self.bombs - correct) + (self.flagged_tiles - correct)
+		};
+
+		match self.options.win_condition {
+			WinCondition::OpenAllSafe => self.tiles_left(),
+			WinCondition::FlagAllMines => flags_left(),
+			WinCondition::Either => self.tiles_left().min(flags_left()),
+		}
+	}
+
+	/// rejects moves once the game has been won or lost
+	fn assert_playing(&self) -> Result<(), UnopenableError> {
+		match self.state() {
+			GameState::Playing => Ok(()),
+			GameState::Won | GameState::Lost => Err(UnopenableError::GameOver),
+		}
+	}
+}
+
+// layout statistics
+impl GameBoard {
+	/// floods the hidden layout from x/y through zeros regardless of visibility, marking every reached tile in seen and returning them in order
+	///
+	/// tiles already marked in seen are not reached again, x/y itself is always included
+	fn flood_opening(&self, x: usize, y: usize, seen: &mut FlatBoard<bool>) -> Vec<(usize, usize)> {
+		seen[y][x] = true;
+
+		let mut region = vec![(x, y)];
+		let mut next = 0;
+
+		while let Some(&(x, y)) = region.get(next) {
+			next += 1;
+
+			// only zeros spread the flood fill, and all tiles around a zero are not bombs
+			if self.board[y][x].tile != Tile::Zero {
+				continue;
+			}
+
+			let (ux, uy) = narrow_xy(x, y);
+
+			for (nx, ny) in self.normalize_around_3x3(ux, uy) {
+				if !std::mem::replace(&mut seen[ny][nx], true) {
+					region.push((nx, ny));
+				}
+			}
+		}
+
+		region
+	}
+
+	/// returns every tile that opening x/y reaches given the cascade option of the board, including tiles that are already open
+	///
+	/// flagged tiles are never opened by a cascade, so they are not reached
+	fn cascade_region(&self, x: u16, y: u16) -> Vec<(usize, usize)> {
+		if !self.options.auto_cascade {
+			return vec![widen_xy(x, y)];
+		}
+
+		let (dim_y, dim_x) = self.board.dimensions();
+		let mut seen = FlatBoard::new(dim_y, dim_x, false);
+
+		for (seen, tile) in seen.iter_backing_mut().zip(self.board.iter_backing()) {
+			*seen = tile.visible == Visibility::Flagged;
+		}
+
+		let (x, y) = widen_xy(x, y);
+
+		self.flood_opening(x, y, &mut seen)
+	}
+
+	/// flags the covered tiles around every number at or next to opened that has exactly as many covered tiles as flags left to place,
+	/// returning the flagged tiles, or does nothing if [`BoardOptions::auto_flag_forced`] is off
+	///
+	/// like the solver this trusts every placed flag, and it never places more flags than there are bombs when flags are limited
+	fn flag_forced_around(&mut self, opened: &[(u16, u16)]) -> Vec<(u16, u16)> {
+		let mut flagged = Vec::new();
+
+		if !self.options.auto_flag_forced {
+			return flagged;
+		}
+
+		let numbers: BTreeSet<(u16, u16)> = opened
+			.iter()
+			.flat_map(|&(x, y)| self.neighbors(x, y).chain([(x, y)]))
+			.filter(|&(x, y)| self.opened_count_at(x, y) > Some(0))
+			.collect();
+
+		for (x, y) in numbers {
+			let count = self.opened_count_at(x, y).unwrap();
+
+			let covered: Vec<_> = self
+				.neighbors(x, y)
+				.filter(|&(nx, ny)| self.get(nx, ny).unwrap().visible == Visibility::NotVisible)
+				.collect();
+
+			if covered.is_empty()
+				|| usize::from(count) != usize::from(self.flags_around(x, y)) + covered.len()
+			{
+				continue;
+			}
+
+			for (cx, cy) in covered {
+				if self.options.limit_flags && self.flagged_tiles >= self.bombs {
+					return flagged;
+				}
+
+				self.get_mut(cx, cy).unwrap().visible = Visibility::Flagged;
+				self.flagged_tiles += 1;
+				flagged.push((cx, cy));
+			}
+		}
+
+		flagged
+	}
+
+	/// opens the non bomb tile at x/y and cascades it if enabled, returning every opened tile without counting them
+	fn open_tile_listed(&mut self, x: u16, y: u16) -> Vec<(u16, u16)> {
+		let (x, y) = widen_xy(x, y);
+
+		// already confirmed bounds using get(y).get(x)
+		self.board[y][x].visible = Visibility::Visible;
+
+		let mut opened = Vec::new();
+		if self.options.auto_cascade {
+			self.open_visible(&mut opened);
+		}
+		// include own tile
+		opened.push(narrow_xy(x, y));
+
+		opened
+	}
+
+	/// returns every opening of the layout, each being a region of connected zeros and the numbers bordering it
+	///
+	/// a number bordering two openings is listed in both, tiles are in flood fill order starting at the topmost leftmost zero
+	#[must_use]
+	pub fn openings(&self) -> Vec<Vec<(u16, u16)>> {
+		let (dim_y, dim_x) = self.board.dimensions();
+		let mut seen = FlatBoard::new(dim_y, dim_x, false);
+
+		let mut openings = vec![];
+
+		for y in 0..dim_y {
+			for x in 0..dim_x {
+				if seen[y][x] || self.board[y][x].tile != Tile::Zero {
+					continue;
+				}
+
+				let region = self.flood_opening(x, y, &mut seen);
+
+				// borders are shared between openings, so they are released to be seen by the next flood fill
+				for &(x, y) in &region {
+					if self.board[y][x].tile != Tile::Zero {
+						seen[y][x] = false;
+					}
+				}
+
+				openings.push(region.into_iter().map(|(x, y)| narrow_xy(x, y)).collect());
+			}
+		}
+
+		openings
+	}
+
+	/// returns the 3BV of the layout, the least amount of clicks that clear the board without flagging
+	///
+	/// every opening of connected zeros takes one click, and every number not bordering a zero takes one more
+	#[must_use]
+	pub fn board_3bv(&self) -> u32 {
+		let (dim_y, dim_x) = self.board.dimensions();
+		let mut seen = FlatBoard::new(dim_y, dim_x, false);
+
+		let mut bv = 0;
+
+		for y in 0..dim_y {
+			for x in 0..dim_x {
+				if seen[y][x] || self.board[y][x].tile != Tile::Zero {
+					continue;
+				}
+
+				bv += 1;
+
+				// the numbers around the opening are cleared by the same click
+				self.flood_opening(x, y, &mut seen);
+			}
+		}
+
+		let lone_numbers = self
+			.board
+			.iter_backing()
+			.zip(seen.iter_backing())
+			.filter(|&(t, &seen)| !seen && !t.tile.is_bomb())
+			.count();
+
+		bv + u32::try_from(lone_numbers).unwrap()
+	}
+
+	/// estimates how hard the board is from its current state
+	///
+	/// guess points come from [`autosolve`] playing a copy of the board, so they are only as good as the solver is strong,
+	/// a stronger solver would guess less often, and a lost guess ends the count early.
+	/// Guesses are drawn from the board seed so the estimate is stable for a given board
+	#[must_use]
+	pub fn difficulty_estimate(&self) -> DifficultyMetrics {
+		let mut rng = StdRng::seed_from_u64(self.seed.unwrap_or_default());
+
+		DifficultyMetrics {
+			density: self.bomb_density(),
+			bv3: self.board_3bv(),
+			guess_points: match self.state() {
+				GameState::Playing => autosolve(&mut self.clone(), &mut rng).guesses,
+				GameState::Won | GameState::Lost => 0,
+			},
+		}
+	}
+}
+
+// end of game
+impl GameBoard {
+	/// ends the game in the failure state if it has not ended yet, and returns every tile revealed by the loss
+	///
+	/// this can be called again after a loss to get the same summary, a won game is left as is and reveals nothing
+	pub fn finalize_loss(&mut self) -> LossReveal {
+		match self.state() {
+			GameState::Playing => self.lose_game(),
+			GameState::Won => return LossReveal::default(),
+			GameState::Lost => {}
+		}
+
+		let mut reveal = LossReveal {
+			detonated: self.detonated,
+			..LossReveal::default()
+		};
+
+		for ((y, x), tile) in self.board.enumerate_2d() {
+			let coord = narrow_xy(x, y);
+
+			if tile.tile.is_bomb() {
+				if Some(coord) != reveal.detonated {
+					reveal.bombs.push(coord);
+				}
+			} else if tile.visible == Visibility::Flagged {
+				reveal.wrong_flags.push(coord);
+			}
+		}
+
+		reveal
+	}
+}
+
+// rule options
+impl GameBoard {
+	/// returns the rule options of this board
+	#[must_use]
+	pub const fn options(&self) -> &BoardOptions {
+		&self.options
+	}
+
+	/// returns the rule options of this board for changing them
+	pub const fn options_mut(&mut self) -> &mut BoardOptions {
+		&mut self.options
+	}
+}
+
+// annotations
+impl GameBoard {
+	/// sets or clears the mark of a tile, returning [`UnopenableError::OutOfBounds`] for out of bounds tiles
+	///
+	/// marks are a pencil layer for the player, unlike flags they are not counted by [`BaseGameBoard::flagged`] and do not affect chording
+	pub fn annotate(&mut self, x: u16, y: u16, mark: Option<char>) -> Result<(), UnopenableError> {
+		let (x, y) = widen_xy(x, y);
+
+		*self
+			.annotations
+			.get_mut(y)
+			.and_then(|row| row.get_mut(x))
+			.ok_or(UnopenableError::OutOfBounds)? = mark;
+
+		Ok(())
+	}
+
+	/// removes every mark on the board
+	pub fn clear_annotations(&mut self) {
+		self.annotations
+			.iter_backing_mut()
+			.for_each(|mark| *mark = None);
+	}
+
+	/// returns the marks of every tile indexed as y/x, including marks on tiles that were opened after they were marked
+	#[must_use]
+	pub const fn annotations(&self) -> &FlatBoard<Option<char>> {
+		&self.annotations
+	}
+}
+
+// visibility snapshots
+impl GameBoard {
+	/// returns the visibility of every tile packed into 2 bits each, for cheap state hashing and syncing
+	#[must_use]
+	pub fn visibility_snapshot(&self) -> VisibilityMask {
+		VisibilityMask::pack(
+			self.dimensions(),
+			self.board.iter_backing().map(|t| t.visible),
+		)
+	}
+
+	/// restores the visibility of every tile from a snapshot without changing the hidden layout, and recomputes the flagged and opened counts and whether the game is lost
+	///
+	/// bombs are only visible once a game is lost, so a snapshot showing every bomb restores a lost game, though not which bomb was hit.
+	/// The board is left unchanged if the snapshot does not fit it, or returns [`RestoreError::Corrupted`] if it shows only some of the bombs
+	pub fn apply_visibility_snapshot(&mut self, mask: &VisibilityMask) -> Result<(), RestoreError> {
+		if mask.dimensions() != self.dimensions() {
+			return Err(RestoreError::DimensionMismatch);
+		}
+
+		let visibilities = (0..self.board.iter_backing().len())
+			.map(|i| mask.get(i))
+			.collect::<Option<Vec<Visibility>>>()
+			.ok_or(RestoreError::Corrupted)?;
+
+		let visible_bombs = self
+			.board
+			.iter_backing()
+			.zip(&visibilities)
+			.filter(|&(t, &v)| t.tile.is_bomb() && v == Visibility::Visible)
+			.count();
+
+		let lost = visible_bombs > 0;
+
+		if lost && visible_bombs != usize::try_from(self.bombs).unwrap() {
+			return Err(RestoreError::Corrupted);
+		}
+
+		self.lost = lost;
+		self.detonated = None;
+		self.flagged_tiles = 0;
+		self.opened_tiles = 0;
+
+		for (tile, visible) in self.board.iter_backing_mut().zip(visibilities) {
+			tile.visible = visible;
+
+			match visible {
+				Visibility::Flagged => self.flagged_tiles += 1,
+				// bombs are only visible once the game is lost and never count as opened
+				Visibility::Visible if !tile.tile.is_bomb() => self.opened_tiles += 1,
+				Visibility::Visible | Visibility::NotVisible => (),
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// board format
+impl GameBoard {
+	/// writes the hidden layout as one line per row of `0` for safe tiles and `1` for bombs, the plain text layout most external solvers and analyzers read
+	///
+	/// **this writes out the hidden layout**, the visible state is not included
+	#[must_use]
+	pub fn to_board_format(&self) -> String {
+		let (dim_y, dim_x) = self.board.dimensions();
+		let mut out = String::with_capacity(dim_y * (dim_x + 1));
+
+		for row in self.board.iter() {
+			out.extend(row.iter().map(|t| if t.tile.is_bomb() { '1' } else { '0' }));
+			out.push('\n');
+		}
+
+		out
+	}
+
+	/// parses a fully hidden board from the text written by [`GameBoard::to_board_format`], which round trips the hidden layout losslessly
+	///
+	/// blank lines and surrounding whitespace are ignored, and every row must be as long as the first
+	pub fn from_board_format(text: &str) -> Result<Self, BoardFormatError> {
+		let mut bombs = Vec::new();
+		let mut width = None;
+		let mut height = 0usize;
+
+		for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+			let mut len = 0usize;
+
+			for (x, c) in line.chars().enumerate() {
+				match c {
+					'0' => (),
+					'1' => bombs.push((x, height)),
+					c => return Err(BoardFormatError::InvalidChar(c)),
+				}
+
+				len += 1;
+			}
+
+			if *width.get_or_insert(len) != len {
+				return Err(BoardFormatError::RaggedRow(height));
+			}
+
+			height += 1;
+		}
+
+		let dimension =
+			|d: usize| u16::try_from(d).map_err(|_| NewBoardError::SizeConstraintOverflow);
+
+		let x = dimension(width.unwrap_or_default())?;
+		let y = dimension(height)?;
+
+		// every bomb coordinate is below the dimensions, which fit into u16
+		let bombs: Vec<_> = bombs
+			.into_iter()
+			.map(|(bx, by)| narrow_xy(bx, by))
+			.collect();
+
+		Ok(Self::with_bombs(x, y, &bombs)?)
+	}
+
+	/// the length of the header of [`GameBoard::encode_layout`], holding the x/y dimensions and bomb count
+	const LAYOUT_HEADER_LEN: usize = 8;
+
+	/// packs the hidden layout into bytes, as a header of the x and y dimensions as little endian u16's and the bomb count as a little endian u32,
+	/// followed by one bit per tile in row major order, lowest bit first, where a set bit is a bomb
+	///
+	/// **this writes out the hidden layout**, the visible state is not included
+	#[must_use]
+	pub fn encode_layout(&self) -> Vec<u8> {
+		let (x, y) = self.dimensions();
+		let tiles = self.board.iter_backing().len();
+
+		let mut out = Vec::with_capacity(Self::LAYOUT_HEADER_LEN + tiles.div_ceil(8));
+
+		out.extend(x.to_le_bytes());
+		out.extend(y.to_le_bytes());
+		out.extend(self.bombs.to_le_bytes());
+
+		let bits: Vec<_> = self
+			.board
+			.iter_backing()
+			.map(|t| t.tile.is_bomb())
+			.collect();
+
+		out.extend(bits.chunks(8).map(|chunk| {
+			chunk
+				.iter()
+				.enumerate()
+				.fold(0u8, |byte, (i, &bomb)| byte | (u8::from(bomb) << i))
+		}));
+
+		out
+	}
+
+	/// unpacks a fully hidden board from the bytes written by [`GameBoard::encode_layout`], which round trips the hidden layout losslessly
+	pub fn decode_layout(bytes: &[u8]) -> Result<Self, DecodeError> {
+		let (header, layout) = bytes
+			.split_at_checked(Self::LAYOUT_HEADER_LEN)
+			.ok_or(DecodeError::Truncated)?;
+
+		let x = u16::from_le_bytes([header[0], header[1]]);
+		let y = u16::from_le_bytes([header[2], header[3]]);
+		let bombs = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+		Self::validate_board(x, y, bombs, None)?;
+
+		let tiles: usize = widening_mul(x, y)
+			.try_into()
+			.expect("area overflowed usize");
+
+		match layout.len().cmp(&tiles.div_ceil(8)) {
+			std::cmp::Ordering::Less => return Err(DecodeError::Truncated),
+			std::cmp::Ordering::Greater => return Err(DecodeError::Oversized),
+			std::cmp::Ordering::Equal => (),
+		}
+
+		let arr: Vec<bool> = (0..tiles)
+			.map(|i| layout[i / 8] & (1 << (i % 8)) != 0)
+			.collect();
+
+		// every bit after the last tile is padding
+		if !tiles.is_multiple_of(8) && layout[tiles / 8] >> (tiles % 8) != 0 {
+			return Err(DecodeError::NonZeroPadding);
+		}
+
+		if arr.iter().filter(|&&b| b).count() != usize::try_from(bombs).unwrap() {
+			return Err(DecodeError::BombCountMismatch);
+		}
+
+		let mut gb = Self::blank_board(x, y, bombs);
+		gb.populate_implant(arr);
+
+		Ok(gb)
+	}
+
+	/// packs the hidden layout and the visibility of every tile, as [`GameBoard::encode_layout`] followed by the bytes of [`GameBoard::visibility_snapshot`]
+	///
+	/// this is enough to resume a game in progress, options, seed and clearing are not saved.
+	/// **this writes out the hidden layout**
+	#[must_use]
+	pub fn save_snapshot(&self) -> Vec<u8> {
+		let mut out = self.encode_layout();
+		out.extend_from_slice(self.visibility_snapshot().as_bytes());
+
+		out
+	}
+
+	/// unpacks a board from the bytes written by [`GameBoard::save_snapshot`], with every tile as visible as it was when saved
+	pub fn load_snapshot(bytes: &[u8]) -> Result<Self, DecodeError> {
+		let header = bytes
+			.get(..Self::LAYOUT_HEADER_LEN)
+			.ok_or(DecodeError::Truncated)?;
+
+		let x = u16::from_le_bytes([header[0], header[1]]);
+		let y = u16::from_le_bytes([header[2], header[3]]);
+
+		let tiles = usize::from(x) * usize::from(y);
+		let (layout, visibility) = bytes
+			.split_at_checked(Self::LAYOUT_HEADER_LEN + tiles.div_ceil(8))
+			.ok_or(DecodeError::Truncated)?;
+
+		let mut gb = Self::decode_layout(layout)?;
+
+		let mask = match visibility.len().cmp(&VisibilityMask::byte_len((x, y))) {
+			std::cmp::Ordering::Less => return Err(DecodeError::Truncated),
+			std::cmp::Ordering::Greater => return Err(DecodeError::Oversized),
+			std::cmp::Ordering::Equal => VisibilityMask::from_bytes((x, y), visibility).unwrap(),
+		};
+
+		gb.apply_visibility_snapshot(&mask)?;
+
+		Ok(gb)
+	}
+}
+
+// assist methods, these read the hidden layout
+#[cfg(feature = "assist")]
+impl GameBoard {
+	/// the value of a bomb in [`GameBoard::count_grid`]
+	pub const COUNT_GRID_BOMB: u8 = u8::MAX;
+
+	/// returns true if opening x/y would not hit a bomb, and false for bombs or out of bounds tiles
+	///
+	/// **this peeks at the hidden layout** and gives away information the player can not know,
+	/// it exists for tutorial and assist modes that warn before a fatal click
+	#[must_use]
+	pub fn is_safe(&self, x: u16, y: u16) -> bool {
+		self.get(x, y).is_some_and(|t| !t.tile.is_bomb())
+	}
+
+	/// returns the count of bombs around x/y, or [`None`] for bombs and out of bounds tiles
+	///
+	/// this is the count precomputed at generation, not a recount of the neighbors.
+	/// **this peeks at the hidden layout** for unopened tiles, use [`BaseGameBoard::opened_count_at`] to only read opened tiles
+	#[must_use]
+	pub fn neighbor_bomb_count(&self, x: u16, y: u16) -> Option<MineCount> {
+		self.get(x, y)?.tile.mine_count()
+	}
+
+	/// returns the stored count of bombs around every tile indexed as y/x, using [`GameBoard::COUNT_GRID_BOMB`] for bombs
+	///
+	/// **this peeks at the hidden layout** regardless of visibility, it exists for debugging generation
+	#[must_use]
+	pub fn count_grid(&self) -> FlatBoard<u8> {
+		let (y, x) = self.board.dimensions();
+
+		let mut grid = FlatBoard::new(y, x, Self::COUNT_GRID_BOMB);
+
+		for (count, tile) in grid.iter_backing_mut().zip(self.board.iter_backing()) {
+			*count = tile.tile.as_count().unwrap_or(Self::COUNT_GRID_BOMB);
+		}
+
+		grid
+	}
+
+	/// returns the count of flags on bombs and the count of flags on safe tiles
+	///
+	/// **this peeks at the hidden layout**, it exists for end of game stats and debugging
+	#[must_use]
+	pub fn flag_accuracy(&self) -> (u32, u32) {
+		self.board
+			.iter_backing()
+			.filter(|t| t.visible == Visibility::Flagged)
+			.fold((0, 0), |(correct, wrong), t| {
+				if t.tile.is_bomb() {
+					(correct + 1, wrong)
+				} else {
+					(correct, wrong + 1)
+				}
+			})
+	}
+}
+
+// core implementation of BaseGameBoard
+impl BaseGameBoard for GameBoard {
+	fn bomb_count(&self) -> u32 {
+		self.bombs
+	}
+
+	fn layout_3bv(&self) -> Option<u32> {
+		Some(self.board_3bv())
+	}
+
+	fn snapshot(&self) -> BoardSnapshot {
+		BoardSnapshot::new(self.clone())
+	}
+
+	fn restore(&mut self, snapshot: &BoardSnapshot) -> Result<(), RestoreError> {
+		self.clone_from(snapshot.downcast_ref().ok_or(RestoreError::WrongBoard)?);
+
+		Ok(())
+	}
+
+	fn annotation(&self, x: u16, y: u16) -> Option<char> {
+		let (x, y) = widen_xy(x, y);
+
+		*self.annotations.get(y)?.get(x)?
+	}
+
+	fn chord_policy(&self) -> ChordPolicy {
+		self.options.chord_policy
+	}
+
+	fn dimensions(&self) -> (u16, u16) {
+		(
+			self.board.dimensions().1.try_into().unwrap(),
+			self.board.len().try_into().unwrap(),
+		)
+	}
+
+	fn flagged(&self) -> u32 {
+		self.flagged_tiles
+	}
+
+	fn opened(&self) -> u32 {
+		self.opened_tiles
+	}
+
+	/// generates a new board with a given clear zone where no bombs will be guaranteed
+	fn with_clearing(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+	) -> Result<Self, NewBoardError> {
+		Self::validate_board(x, y, bombs, (clearx, cleary))?;
+
+		Self::with_clearings(x, y, bombs, &[(clearx, cleary)])
+	}
+
+	fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Self::with_clearing_seeded(x, y, bombs, clearx, cleary, seed)
+	}
+
+	/// replaces this board with a new board generated without a clearing, keeping the rule options
+	fn reset_with(&mut self, x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
+		let options = self.options;
+
+		*self = Self::new(x, y, bombs)?;
+		self.options = options;
+
+		Ok(())
+	}
+
+	/// opens the 8 tiles around an opened number, a bomb among them loses the game before anything is opened
+	fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		self.assert_playing()?;
+		let openable = self.normalize_around_3x3(x, y);
+
+		let mut opened = Vec::with_capacity(openable.len());
+
+		let flags = self.flags_around(x, y);
+		let center = self.tile_or_unopenable(x, y)?;
+
+		if center.visible != Visibility::Visible {
+			return Err(UnopenableError::NotANumber);
+		}
+
+		let count = center.tile.as_count().ok_or(UnopenableError::NotANumber)?;
+
+		match (self.options.chord_policy, flags.cmp(&count)) {
+			(_, Ordering::Equal) | (ChordPolicy::Lenient, Ordering::Greater) => (),
+			(ChordPolicy::Lenient, Ordering::Less) => return Ok(GameBoardEvent::from(opened)),
+			(ChordPolicy::Strict, _) => return Err(UnopenableError::FlagCountMismatch),
+		}
+
+		// if any notvisible tile we are trying to open is a bomb raise error before opening anything
+		for &(x, y) in &openable {
+			let tile = self.board[y][x];
+
+			if tile.visible == Visibility::NotVisible && tile.tile.is_bomb() {
+				let (bx, by) = narrow_xy(x, y);
+
+				return Err(self.detonate(bx, by));
+			}
+		}
+
+		for &(x, y) in &openable {
+			let tile = self.board[y][x];
+
+			match tile.visible {
+				// ignore visible tiles
+				// don't attempt to open flagged tiles
+				Visibility::Visible | Visibility::Flagged => (),
+				Visibility::NotVisible => {
+					self.board[y][x].visible = Visibility::Visible;
+					opened.push(narrow_xy(x, y));
+				}
+			}
+		}
+
+		// open visible tiles to complete cycle
+		if self.options.auto_cascade {
+			self.open_visible(&mut opened);
+		}
+
+		self.opened_tiles += u32::try_from(opened.len()).unwrap();
+
+		let flagged = self.flag_forced_around(&opened);
+
+		Ok(GameBoardEvent::from(opened).with_flags(flagged))
+	}
+
+	/// opens the given tile, a bomb under it loses the game
+	fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		self.assert_openable(x, y)?;
+
+		let kept = self.options.compact_undo.then(|| {
+			self.cascade_region(x, y)
+				.into_iter()
+				.filter(|&(kx, ky)| self.board[ky][kx].visible == Visibility::Visible)
+				.map(|(kx, ky)| narrow_xy(kx, ky))
+				.collect()
+		});
+
+		let opened = self.open_tile_listed(x, y);
+		self.opened_tiles += u32::try_from(opened.len()).unwrap();
+
+		let flagged = self.flag_forced_around(&opened);
+
+		let event = kept.map_or_else(
+			|| opened.into(),
+			|kept| GameBoardEvent::OpenSeed { x, y, kept },
+		);
+
+		Ok(event.with_flags(flagged))
+	}
+
+	/// flags or unflags a tile depending on whether it is flagged already
+	/// errors on an already open tile
+	fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		self.assert_playing()?;
+		let tile = self.tile_or_unopenable(x, y)?;
+		let (bx, by) = widen_xy(x, y);
+
+		match tile.visible {
+			Visibility::Visible => Err(UnopenableError::AlreadyOpen),
+			Visibility::Flagged => {
+				self.board[by][bx].visible = Visibility::NotVisible;
+				self.flagged_tiles -= 1;
+				Ok(GameBoardEvent::flag_tile(x, y))
+			}
+			Visibility::NotVisible
+				if self.options.limit_flags && self.flagged_tiles >= self.bombs =>
+			{
+				Err(UnopenableError::FlagLimitReached)
+			}
+			Visibility::NotVisible => {
+				self.board[by][bx].visible = Visibility::Flagged;
+				self.flagged_tiles += 1;
+				Ok(GameBoardEvent::flag_tile(x, y))
+			}
+		}
+	}
+
+	/// gets a specific tile on the board for public inspection
+	fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
+		if self.shown_detonation() == Some((x, y)) {
+			return Some(VisibleTile::DetonatedBomb);
+		}
+
+		let (x, y) = widen_xy(x, y);
+
+		Some(self.visible_tile(*self.board.get(y)?.get(x)?))
+	}
+
+	/// undoes a move specified by a gameboard event
+	fn undo_move(&mut self, event: &GameBoardEvent) -> Result<(), UndoError> {
+		match event {
+			&GameBoardEvent::ToggleFlagCell(x, y) => {
+				self.get_mut(x, y)
+					.ok_or(UndoError::OutOfBounds)?
+					.swap_flag()
+					.or(Err(UndoError::AlreadyOpen))?;
+				if self.get(x, y).unwrap().visible == Visibility::NotVisible {
+					self.flagged_tiles -= 1;
+				} else {
+					self.flagged_tiles += 1;
 				}
 			}
 			GameBoardEvent::OpenCell(cells) => {
+				// validate every cell before closing any, so a failed undo leaves the board unchanged
 				for (x, y) in cells.iter().copied() {
-					let tile = self.get_mut(x, y).ok_or(UndoError::OutOfBounds)?;
+					let tile = self.get(x, y).ok_or(UndoError::OutOfBounds)?;
 
-					if Visibility::Visible == tile.visible {
+					if tile.visible != Visibility::Visible {
+						return Err(UndoError::AlreadyClosed);
+					}
+				}
+
+				for (x, y) in cells.iter().copied() {
+					let tile = self.get_mut(x, y).unwrap();
+
+					// a repeated cell is only closed once
+					if tile.visible == Visibility::Visible {
 						tile.visible = Visibility::NotVisible;
 						self.opened_tiles -= 1;
-					} else {
-						return Err(UndoError::AlreadyClosed);
 					}
 				}
 			}
+			&GameBoardEvent::OpenSeed { x, y, ref kept } => {
+				self.get(x, y).ok_or(UndoError::OutOfBounds)?;
+
+				// the region is recomputed after the move, when every reached tile is open
+				let cells: Vec<_> = self
+					.cascade_region(x, y)
+					.into_iter()
+					.map(|(cx, cy)| narrow_xy(cx, cy))
+					.filter(|c| !kept.contains(c))
+					.collect();
+
+				if cells
+					.iter()
+					.any(|&(cx, cy)| self.get(cx, cy).unwrap().visible != Visibility::Visible)
+				{
+					return Err(UndoError::AlreadyClosed);
+				}
+
+				for (cx, cy) in cells {
+					self.get_mut(cx, cy).unwrap().visible = Visibility::NotVisible;
+					self.opened_tiles -= 1;
+				}
+			}
+			GameBoardEvent::Sequence(events) => {
+				for event in events.iter().rev() {
+					self.undo_move(event)?;
+				}
+			}
 		}
 
 		Ok(())
@@ -495,28 +1821,117 @@ impl BaseGameBoard for GameBoard {
 		for j in board.iter_backing_mut() {
 			let tile = it.next().expect("Sizes were not correctly constrained");
 
-			*j = match tile.visible {
-				Visibility::NotVisible => VisibleTile::NotVisible,
-				Visibility::Visible => VisibleTile::Visible(tile.tile),
-				Visibility::Flagged => VisibleTile::Flagged,
-			};
+			*j = self.visible_tile(*tile);
+		}
+
+		if let Some((x, y)) = self.shown_detonation() {
+			board[y.into()][usize::from(x)] = VisibleTile::DetonatedBomb;
 		}
 
 		board
 	}
 
+	/// checks the game against the [`WinCondition`] of this board, returning how many moves are still needed to win on failure
+	///
+	/// for [`WinCondition::FlagAllMines`] this is the count of unflagged bombs plus wrong flags
 	fn win_game(&mut self) -> Result<(), u32> {
-		todo!()
+		match self.left_to_win() {
+			0 => Ok(()),
+			left => Err(left),
+		}
+	}
+
+	fn state(&self) -> GameState {
+		if self.lost {
+			GameState::Lost
+		} else if self.left_to_win() == 0 {
+			GameState::Won
+		} else {
+			GameState::Playing
+		}
 	}
 	fn lose_game(&mut self) {
 		if std::mem::replace(&mut self.lost, true) {
 			return;
 		}
 
+		// correct flags stay flagged, like a classic reveal
 		for i in self.board.iter_backing_mut() {
-			if i.tile.is_bomb() {
+			if i.tile.is_bomb() && i.visible != Visibility::Flagged {
 				i.visible = Visibility::Visible;
 			}
 		}
 	}
 }
+
+/// a [`GameBoard`] formatted with a custom per tile style, see [`GameBoard::display_with`]
+struct StyledBoard<'a> {
+	board: &'a GameBoard,
+	style: &'a dyn Fn(VisibleTile) -> String,
+}
+
+impl fmt::Display for StyledBoard<'_> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for row in self.board.render().iter() {
+			for &tile in row {
+				f.write_str(&(self.style)(tile))?;
+			}
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
+}
+
+/// the style used by the [`fmt::Display`] impl of [`GameBoard`], which renders opened tiles with the ansi colors of [`Tile`]
+fn ansi_style(v: VisibleTile) -> String {
+	match v {
+		VisibleTile::Visible(tile) => format!("{tile}\u{1b}[0m"),
+		VisibleTile::DetonatedBomb => format!("\u{1b}[41m{} \u{1b}[0m", Glyphs::DEFAULT.mine_hit),
+		VisibleTile::NotVisible => String::from("##"),
+		VisibleTile::Flagged => format!("{} ", Glyphs::DEFAULT.flag),
+		VisibleTile::WrongFlag => format!("\u{1b}[31m{} \u{1b}[0m", Glyphs::DEFAULT.wrong_flag),
+	}
+}
+
+// formatting
+impl GameBoard {
+	/// returns a [`fmt::Display`] implementor that walks the rendered board row by row, formatting each tile with style and ending each row with a newline
+	///
+	/// this allows plugging in any per tile rendering (plain text, emoji, braille) while reusing the grid layout of the default [`fmt::Display`] impl
+	pub fn display_with<'a>(
+		&'a self,
+		style: &'a dyn Fn(VisibleTile) -> String,
+	) -> impl fmt::Display + 'a {
+		StyledBoard { board: self, style }
+	}
+
+	/// returns every tile as it would be rendered paired with its x/y, in the row major order of [`BaseGameBoard::render`]
+	///
+	/// this allows building custom export formats without allocating a rendered board or nesting loops
+	pub fn cells(&self) -> impl Iterator<Item = ((u16, u16), VisibleTile)> + '_ {
+		let (_, dim_x) = self.board.dimensions();
+		let detonation = self.shown_detonation();
+
+		self.board
+			.iter_backing()
+			.enumerate()
+			.map(move |(idx, &tile)| {
+				let xy = narrow_xy(idx % dim_x, idx / dim_x);
+
+				match detonation {
+					Some(detonated) if detonated == xy => (xy, VisibleTile::DetonatedBomb),
+					_ => (xy, self.visible_tile(tile)),
+				}
+			})
+	}
+}
+
+impl fmt::Display for GameBoard {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.display_with(&ansi_style).fmt(f)
+	}
+}
+
+#[cfg(test)]
+mod tests;
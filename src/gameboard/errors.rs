@@ -1,6 +1,5 @@
-//! Base errors that a [BaseGameBoard][super::BaseGameBoard] can return
+//! Base errors that a [`BaseGameBoard`][super::BaseGameBoard] can return
 
-use super::tiles::Tile;
 use thiserror::Error;
 
 /// an error returned when creation of a new board fails
@@ -12,13 +11,28 @@ pub enum NewBoardError {
 	ZeroDimension,
 	#[error("exceeded one or more dimensional limits (10k max x/y, 100m max bombs), or clearing zone was out of bounds")]
 	SizeConstraintOverflow,
+	#[error(
+		"no board satisfying the requested constraints was generated within the attempt limit"
+	)]
+	AttemptsExhausted,
+	#[error("a given bomb coordinate was out of bounds")]
+	BombOutOfBounds,
+	#[error("a given bomb coordinate was repeated")]
+	DuplicateBomb,
+	#[error("the bomb density was not in the range [0,1]")]
+	InvalidDensity,
+	#[error("the tiles of the board would exceed the memory budget")]
+	MemoryBudgetExceeded,
+	#[error("the board was created but opening its clearing failed")]
+	InitialOpenFailed,
 }
 
 /// an error returned when during normal play an exception is reached, which may or may not be a game over state
 #[derive(Error, Debug)]
 pub enum UnopenableError {
-	#[error("a bomb was under this tile")]
-	BombHit,
+	/// carries the x/y of the bomb that was hit
+	#[error("a bomb was under the tile at {0}/{1}")]
+	BombHit(u16, u16),
 	#[error("this tile is already open")]
 	AlreadyOpen,
 	#[error("this tile is flagged")]
@@ -29,9 +43,21 @@ pub enum UnopenableError {
 	FlagCountMismatch,
 	#[error("game has already ended")]
 	GameOver,
+	#[error("as many flags as bombs have already been placed")]
+	FlagLimitReached,
+	#[error("this tile is not an opened number")]
+	NotANumber,
+	#[error("the rest of the board can not be deduced without guessing")]
+	NotDeducible,
+	/// carries the x/y of a bomb in a set of tiles that was refused as a whole, the game is not lost
+	#[error("a bomb was under the tile at {0}/{1} in the region")]
+	RegionContainsBomb(u16, u16),
+	/// a lazily created board could not be generated around the first move
+	#[error("the board could not be generated around this tile: {0}")]
+	GenerationFailed(NewBoardError),
 }
 
-/// an error returned when the [BaseGameBoard][super::BaseGameBoard] failed to undo a move
+/// an error returned when the [`BaseGameBoard`][super::BaseGameBoard] failed to undo a move
 #[derive(Error, Debug)]
 pub enum UndoError {
 	#[error("a tile is out of bounds")]
@@ -42,10 +68,50 @@ pub enum UndoError {
 	AlreadyOpen,
 }
 
-/// returns a [UnopenableError::BombHit] if the tile is a bomb
-pub const fn assert_not_bomb(t: Tile) -> Result<(), UnopenableError> {
-	match t.is_bomb() {
-		true => Err(UnopenableError::BombHit),
-		false => Ok(()),
-	}
+/// an error returned when two rendered boards can not be compared
+#[derive(Error, Debug)]
+pub enum DiffError {
+	#[error("the boards have different dimensions")]
+	DimensionMismatch,
+}
+
+/// an error returned when a snapshot could not be restored onto a board
+#[derive(Error, Debug)]
+pub enum RestoreError {
+	#[error("the snapshot was taken of a board with different dimensions")]
+	DimensionMismatch,
+	#[error("the snapshot contains invalid data")]
+	Corrupted,
+	#[error("the snapshot was taken of a different kind of board")]
+	WrongBoard,
+	#[error("this board does not support full snapshots")]
+	Unsupported,
+}
+
+/// an error returned when a board could not be parsed from [`GameBoard::to_board_format`][super::GameBoard::to_board_format] text
+#[derive(Error, Debug)]
+pub enum BoardFormatError {
+	#[error("row {0} has a different length than the first row")]
+	RaggedRow(usize),
+	#[error("an unexpected character {0:?} was found, only 0 and 1 are allowed")]
+	InvalidChar(char),
+	#[error("the parsed layout is not a valid board: {0}")]
+	Board(#[from] NewBoardError),
+}
+
+/// an error returned when a board could not be decoded from [`GameBoard::encode_layout`][super::GameBoard::encode_layout] bytes
+#[derive(Error, Debug)]
+pub enum DecodeError {
+	#[error("the buffer ended before the header or layout did")]
+	Truncated,
+	#[error("the buffer has bytes after the end of the layout")]
+	Oversized,
+	#[error("the unused bits after the last tile were not zero")]
+	NonZeroPadding,
+	#[error("the bomb count in the header does not match the layout")]
+	BombCountMismatch,
+	#[error("the decoded layout is not a valid board: {0}")]
+	Board(#[from] NewBoardError),
+	#[error("the decoded visibility does not fit the layout: {0}")]
+	Visibility(#[from] RestoreError),
 }
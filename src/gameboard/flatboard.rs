@@ -1,7 +1,7 @@
 use std::iter::repeat;
 use std::ops::{Index, IndexMut};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct FlatBoard<T> {
 	dim_1: usize,
 	dim_2: usize,
@@ -56,27 +56,28 @@ impl<T: Clone> FlatBoard<T> {
 		Self {
 			dim_1,
 			dim_2,
-			data: repeat(default).take(array_len).collect(),
+			data: std::iter::repeat_n(default, array_len).collect(),
 		}
 	}
 }
 
 impl<T: Default> FlatBoard<T> {
 	/// generates a new 2d array using T's default method as fill in
+	#[must_use]
 	pub fn new_default(dim_1: usize, dim_2: usize) -> Self {
 		let array_len = Self::array_length(dim_1, dim_2);
 
 		Self {
 			dim_1,
 			dim_2,
-			data: repeat(()).map(|_| T::default()).take(array_len).collect(),
+			data: repeat(()).map(|()| T::default()).take(array_len).collect(),
 		}
 	}
 }
 
 impl<T> FlatBoard<T> {
 	/// helper method to return an arrays length from its 2 dimensions or panic on overflow
-	fn array_length(dim_1: usize, dim_2: usize) -> usize {
+	const fn array_length(dim_1: usize, dim_2: usize) -> usize {
 		dim_1
 			.checked_mul(dim_2)
 			.expect("array length overflowed usize")
@@ -84,18 +85,28 @@ impl<T> FlatBoard<T> {
 
 	/// returns dimensions of flatboard
 	#[inline]
+	#[must_use]
 	pub const fn dimensions(&self) -> (usize, usize) {
 		(self.dim_1, self.dim_2)
 	}
 
-	/// returns dim_1 as len
+	/// returns `dim_1` as len
 	#[inline]
+	#[must_use]
 	pub const fn len(&self) -> usize {
 		self.dim_1
 	}
 
+	/// returns true if the board has no rows
+	#[inline]
+	#[must_use]
+	pub const fn is_empty(&self) -> bool {
+		self.dim_1 == 0
+	}
+
 	/// gets a reference to a [`Row`] of T from the board at the given index, or returns [`None`] on out of bounds
 	#[inline]
+	#[must_use]
 	pub fn get(&self, idx: usize) -> Option<&Row<T>> {
 		if self.dim_1 <= idx {
 			None
@@ -129,6 +140,29 @@ impl<T> FlatBoard<T> {
 	pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut Row<T>> {
 		self.data.chunks_mut(self.dim_2)
 	}
+
+	/// iterates over every element in sequence along with its `(dim_1, dim_2)` index, which is (y, x) for boards indexed as y/x
+	#[inline]
+	pub fn enumerate_2d(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+		let dim_2 = self.dim_2;
+
+		// an empty dim_2 means an empty backing, so the division is never reached
+		self.data
+			.iter()
+			.enumerate()
+			.map(move |(i, v)| ((i / dim_2, i % dim_2), v))
+	}
+
+	/// iterates mutably over every element in sequence along with its `(dim_1, dim_2)` index, see [`FlatBoard::enumerate_2d`]
+	#[inline]
+	pub fn enumerate_2d_mut(&mut self) -> impl Iterator<Item = ((usize, usize), &mut T)> {
+		let dim_2 = self.dim_2;
+
+		self.data
+			.iter_mut()
+			.enumerate()
+			.map(move |(i, v)| ((i / dim_2, i % dim_2), v))
+	}
 }
 
 use std::slice::{Iter, IterMut};
@@ -150,13 +184,12 @@ impl<T> Index<usize> for FlatBoard<T> {
 
 	#[inline]
 	fn index(&self, index: usize) -> &Self::Output {
-		match self.get(index) {
-			None => panic!(
+		self.get(index).unwrap_or_else(|| {
+			panic!(
 				"Index {index} out of bounds (FlatBoard has a length of {})",
 				self.dim_1
-			),
-			Some(v) => v,
-		}
+			)
+		})
 	}
 }
 
@@ -166,12 +199,31 @@ impl<T> IndexMut<usize> for FlatBoard<T> {
 		// hack around get_mut mutable borrow invalidating immutable borrows to self
 		let dim_1 = self.dim_1;
 
-		match self.get_mut(index) {
-			None => panic!(
-				"Index {index} out of bounds (FlatBoard has a length of {})",
-				dim_1
-			),
-			Some(v) => v,
+		self.get_mut(index).unwrap_or_else(|| {
+			panic!("Index {index} out of bounds (FlatBoard has a length of {dim_1})")
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn enumerate_2d_matches_indexing() {
+		let mut board = FlatBoard::new(2, 3, 0usize);
+
+		for ((d1, d2), v) in board.enumerate_2d_mut() {
+			*v = d1 * 10 + d2;
 		}
+
+		assert_eq!(board.enumerate_2d().count(), 6);
+
+		for ((d1, d2), &v) in board.enumerate_2d() {
+			assert_eq!(board[d1][d2], v);
+			assert_eq!(v, d1 * 10 + d2);
+		}
+
+		assert_eq!(FlatBoard::<u8>::new(3, 0, 0).enumerate_2d().count(), 0);
 	}
 }
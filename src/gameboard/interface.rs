@@ -1,13 +1,34 @@
-use super::errors::{NewBoardError, UndoError, UnopenableError};
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::ops::Range;
+
+use crate::diag::{log_debug, log_warn};
+
+use super::errors::{DiffError, NewBoardError, RestoreError, UndoError, UnopenableError};
 use super::flatboard::{FlatBoard, IterBackingMut};
-use super::tiles::VisibleTile;
+use super::options::ChordPolicy;
+use super::tiles::{Glyphs, MineCount, VisibleTile};
 
 /// an event that gives full detail to undo the action in an efficient manner, at the cost of memory use.
+#[derive(Clone, Debug, Eq, PartialEq)]
 pub enum GameBoardEvent {
 	/// a opening of a set of cells, represented by an array of x/y coordinates
 	OpenCell(Box<[(u16, u16)]>),
 	/// a flag/unflag of a cell
 	ToggleFlagCell(u16, u16),
+	/// a compact opening of the cell at x/y, where undoing recomputes the opened cells from the hidden layout instead of storing them
+	///
+	/// kept lists the cells the opening reached that were already open, which stay open on undo, and is usually empty.
+	/// This event is only undone correctly on the board that created it, with every later move undone first
+	OpenSeed {
+		x: u16,
+		y: u16,
+		kept: Box<[(u16, u16)]>,
+	},
+	/// several events made by a single move, such as an opening followed by automatic flags, which are undone last to first
+	Sequence(Box<[Self]>),
 }
 
 impl From<Vec<(u16, u16)>> for GameBoardEvent {
@@ -17,9 +38,51 @@ impl From<Vec<(u16, u16)>> for GameBoardEvent {
 }
 
 impl GameBoardEvent {
+	#[must_use]
 	pub const fn flag_tile(x: u16, y: u16) -> Self {
 		Self::ToggleFlagCell(x, y)
 	}
+
+	/// returns self followed by a flag of every x/y in flagged as a [`GameBoardEvent::Sequence`], or self unchanged if flagged is empty
+	#[must_use]
+	pub fn with_flags(self, flagged: Vec<(u16, u16)>) -> Self {
+		if flagged.is_empty() {
+			return self;
+		}
+
+		Self::Sequence(
+			std::iter::once(self)
+				.chain(flagged.into_iter().map(|(x, y)| Self::flag_tile(x, y)))
+				.collect(),
+		)
+	}
+
+	/// returns the [`bounding_box`] of every tile this event changed, for repainting only that rectangle
+	///
+	/// returns [`None`] for events that changed nothing, and for events containing a [`GameBoardEvent::OpenSeed`] which does not list the tiles it opened
+	pub fn bounding_box(&self) -> Option<((u16, u16), (u16, u16))> {
+		match *self {
+			Self::OpenCell(ref cells) => bounding_box(cells),
+			Self::ToggleFlagCell(x, y) => Some(((x, y), (x, y))),
+			Self::OpenSeed { .. } => None,
+			Self::Sequence(ref events) => {
+				if events
+					.iter()
+					.any(|event| matches!(event, Self::OpenSeed { .. }))
+				{
+					return None;
+				}
+
+				let corners: Vec<(u16, u16)> = events
+					.iter()
+					.filter_map(Self::bounding_box)
+					.flat_map(<[(u16, u16); 2]>::from)
+					.collect();
+
+				bounding_box(&corners)
+			}
+		}
+	}
 }
 
 #[inline]
@@ -27,7 +90,124 @@ fn widening_mul(a: u16, b: u16) -> u32 {
 	u32::from(a) * u32::from(b)
 }
 
-#[derive(Copy, Clone, Debug)]
+/// converts the usize x/y of a tile on a board back into its coordinates
+///
+/// # Panics
+/// if x or y does not fit in a u16, which never happens for a tile of a board
+#[must_use]
+pub fn narrow_xy(x: usize, y: usize) -> (u16, u16) {
+	let narrow = |v| u16::try_from(v).expect("tile index exceeds the u16 board dimensions");
+
+	(narrow(x), narrow(y))
+}
+
+/// yields every in bounds x/y coordinate in the 3x3 grid around a tile on a board of the given x/y dimensions, excluding the tile itself
+pub fn tiles_around(
+	x: u16,
+	y: u16,
+	(dim_x, dim_y): (u16, u16),
+) -> impl Iterator<Item = (u16, u16)> {
+	(y.saturating_sub(1)..=y.saturating_add(1))
+		.flat_map(move |ny| (x.saturating_sub(1)..=x.saturating_add(1)).map(move |nx| (nx, ny)))
+		.filter(move |&(nx, ny)| nx < dim_x && ny < dim_y)
+		.filter(move |&coord| coord != (x, y))
+}
+
+/// returns the smallest rectangle containing every x/y in coords as its inclusive min and max corners, or [`None`] if coords is empty
+#[must_use]
+pub fn bounding_box(coords: &[(u16, u16)]) -> Option<((u16, u16), (u16, u16))> {
+	let (&first, rest) = coords.split_first()?;
+
+	Some(rest.iter().fold(
+		(first, first),
+		|((min_x, min_y), (max_x, max_y)), &(x, y)| {
+			((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+		},
+	))
+}
+
+/// lists every tile that changed between two renders of the same board as x/y and the new tile, in row major order
+///
+/// this allows incremental rendering and syncing without resending the whole board
+pub fn diff(
+	prev: &FlatBoard<VisibleTile>,
+	next: &FlatBoard<VisibleTile>,
+) -> Result<Vec<(u16, u16, VisibleTile)>, DiffError> {
+	if prev.dimensions() != next.dimensions() {
+		return Err(DiffError::DimensionMismatch);
+	}
+
+	let mut changed = Vec::new();
+
+	for (y, (prev_row, next_row)) in prev.iter().zip(next.iter()).enumerate() {
+		for (x, (&old, &new)) in prev_row.iter().zip(next_row).enumerate() {
+			if old != new {
+				let (x, y) = narrow_xy(x, y);
+				changed.push((x, y, new));
+			}
+		}
+	}
+
+	Ok(changed)
+}
+
+/// the state of a game, as reported by [`BaseGameBoard::state`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameState {
+	/// the game has not ended yet
+	Playing,
+	/// every non bomb tile has been opened
+	Won,
+	/// the game was lost with [`BaseGameBoard::lose_game`]
+	Lost,
+}
+
+/// the effect of a [`KeyEvent`] processed by [`BaseGameBoard::do_event_outcome`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct EventOutcome {
+	/// how many tiles the event opened
+	pub opened: u32,
+	/// the state of the game after the event
+	pub game_state: GameState,
+}
+
+/// the count of tiles in each visible state, as returned by [`BaseGameBoard::tile_census`]
+///
+/// `opened + flagged + hidden` is always the area of the board
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct TileCensus {
+	/// tiles that are visible, including revealed bombs
+	pub opened: u32,
+	/// flagged tiles, including wrong flags
+	pub flagged: u32,
+	/// tiles that are neither opened nor flagged
+	pub hidden: u32,
+}
+
+/// the complete state of a board captured by [`BaseGameBoard::snapshot`], which only [`BaseGameBoard::restore`] on the same kind of board can read
+pub struct BoardSnapshot(Box<dyn Any + Send + Sync>);
+
+impl BoardSnapshot {
+	/// wraps the state of a board, for implementors of [`BaseGameBoard::snapshot`]
+	pub fn new<T: Any + Send + Sync>(state: T) -> Self {
+		Self(Box::new(state))
+	}
+
+	/// returns the wrapped state if it is a T, for implementors of [`BaseGameBoard::restore`]
+	#[must_use]
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.0.downcast_ref()
+	}
+}
+
+impl fmt::Debug for BoardSnapshot {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("BoardSnapshot").finish_non_exhaustive()
+	}
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum KeyEvent {
 	Mouse1(u16, u16),
 	Mouse2(u16, u16),
@@ -41,39 +221,23 @@ pub fn BaseGameBoard_do_event<T: BaseGameBoard>(
 	t: &mut T,
 	k: KeyEvent,
 ) -> Result<(), UnopenableError> {
-	use KeyEvent::*;
+	use KeyEvent::{Mouse1, Mouse2};
 
-	match k {
-		Mouse1(x, y) => {
-			let tile = t.get_board_tile(x, y).ok_or(UnopenableError::OutOfBounds)?;
+	let res = match k {
+		Mouse1(x, y) => match t.open_or_chord(x, y) {
+			// clicking a flag does nothing
+			Ok(_) | Err(UnopenableError::FlaggedTile) => Ok(()),
+			Err(err) => Err(err),
+		},
+		Mouse2(x, y) => t.flag_or_chord(x, y).map(drop),
+		_ => Ok(()),
+	};
 
-			match tile {
-				VisibleTile::NotVisible => {
-					t.open_tile(x, y)?;
-				}
-				VisibleTile::Visible(_) => {
-					t.open_around(x, y)?;
-				}
-				VisibleTile::Flagged => {}
-			}
-		}
-		Mouse2(x, y) => {
-			let tile = t.get_board_tile(x, y).ok_or(UnopenableError::OutOfBounds)?;
-
-			match tile {
-				VisibleTile::NotVisible => {
-					t.flag_tile(x, y)?;
-				}
-				VisibleTile::Flagged => {
-					t.flag_tile(x, y)?;
-				}
-				VisibleTile::Visible(_) => {}
-			}
-		}
-		_ => {}
+	if let Err(ref err) = res {
+		log_warn!("invalid move {k:?}: {err}");
 	}
 
-	Ok(())
+	res
 }
 
 pub trait BaseGameBoard: Sized {
@@ -97,14 +261,40 @@ pub trait BaseGameBoard: Sized {
 		clear_y: u16,
 	) -> Result<Self, NewBoardError>;
 
-	/// opens a tile
+	/// generates a new board like [`BaseGameBoard::with_clearing`] from a seed
+	///
+	/// the default impl ignores the seed and calls [`BaseGameBoard::with_clearing`],
+	/// only implementors that override this generate the same layout for the same seed
+	fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clear_x: u16,
+		clear_y: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		let _ = seed;
+		Self::with_clearing(x, y, bombs, clear_x, clear_y)
+	}
+
+	/// replaces this board with a new unplayed board of the given size, leaving self unchanged on error
+	///
+	/// the default impl regenerates the board through [`BaseGameBoard::with_clearing`] with a clearing at the center,
+	/// implementors that can be created without a clearing or that carry state of their own should override this
+	fn reset_with(&mut self, x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
+		*self = Self::with_clearing(x, y, bombs, x / 2, y / 2)?;
+
+		Ok(())
+	}
+
+	/// opens a tile, hitting a bomb returns [`UnopenableError::BombHit`] at the bomb
 	fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError>;
-	/// opens the 8 tiles surrounding a tile
+	/// opens the 8 tiles surrounding an opened number, other tiles return [`UnopenableError::NotANumber`]
 	fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError>;
 	/// flags or unflags a given tile
 	fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError>;
 
-	/// undoes a move in the board state specified by a GameBoardEvent
+	/// undoes a move in the board state specified by a `GameBoardEvent`
 	fn undo_move(&mut self, event: &GameBoardEvent) -> Result<(), UndoError>;
 
 	/// gets a tile on the board
@@ -115,15 +305,127 @@ pub trait BaseGameBoard: Sized {
 
 	/// checks if a game was won and returns a result designating success or failure to win
 	///
-	/// the [`Err`] case returns a [`u32`] representing how many tiles are still closed and not bombs, can be expressed as area - bomb_count - opened
+	/// the [`Err`] case returns a [`u32`] representing how many tiles are still closed and not bombs, can be expressed as `area - bomb_count - opened`.
+	/// boards with other win conditions may instead return how many other moves are left
 	fn win_game(&mut self) -> Result<(), u32>;
 
-	/// processes a KeyEvent using mouse 1/2, default impl ignores other events
+	/// returns whether the game is still being played, was won, or was lost
+	fn state(&self) -> GameState;
+
+	/// returns true if [`BaseGameBoard::state`] is [`GameState::Won`]
+	#[inline]
+	fn is_won(&self) -> bool {
+		self.state() == GameState::Won
+	}
+
+	/// returns true if [`BaseGameBoard::state`] is [`GameState::Lost`]
+	#[inline]
+	fn is_lost(&self) -> bool {
+		self.state() == GameState::Lost
+	}
+
+	/// opens a hidden tile, or opens around an opened number, which is what a left click does
+	///
+	/// flagged tiles return [`UnopenableError::FlaggedTile`] and tiles of a finished game return [`UnopenableError::GameOver`]
+	fn open_or_chord(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		match self
+			.get_board_tile(x, y)
+			.ok_or(UnopenableError::OutOfBounds)?
+		{
+			VisibleTile::NotVisible => self.open_tile(x, y),
+			VisibleTile::Visible(_) => self.open_around(x, y),
+			VisibleTile::Flagged => Err(UnopenableError::FlaggedTile),
+			VisibleTile::DetonatedBomb | VisibleTile::WrongFlag => Err(UnopenableError::GameOver),
+		}
+	}
+
+	/// returns the tiles that chording x/y with [`BaseGameBoard::open_around`] would open without changing the board, for previewing a chord
+	///
+	/// only the visible state is read, so the preview does not include tiles a cascade would open after the chord,
+	/// and does not know whether a wrong flag makes the chord hit a bomb.
+	/// Returns [`UnopenableError::NotANumber`] for unopened tiles, otherwise the same errors as [`BaseGameBoard::open_or_chord`],
+	/// and a flag count mismatch is handled by the [`BaseGameBoard::chord_policy`] like the chord would
+	fn preview_chord(&self, x: u16, y: u16) -> Result<Vec<(u16, u16)>, UnopenableError> {
+		if self.state() != GameState::Playing {
+			return Err(UnopenableError::GameOver);
+		}
+
+		let count = match self
+			.get_board_tile(x, y)
+			.ok_or(UnopenableError::OutOfBounds)?
+		{
+			VisibleTile::Visible(tile) => tile.as_count().ok_or(UnopenableError::NotANumber)?,
+			VisibleTile::NotVisible => return Err(UnopenableError::NotANumber),
+			VisibleTile::Flagged => return Err(UnopenableError::FlaggedTile),
+			VisibleTile::DetonatedBomb | VisibleTile::WrongFlag => {
+				return Err(UnopenableError::GameOver)
+			}
+		};
+
+		match (self.chord_policy(), self.flags_around(x, y).cmp(&count)) {
+			(_, Ordering::Equal) | (ChordPolicy::Lenient, Ordering::Greater) => (),
+			(ChordPolicy::Lenient, Ordering::Less) => return Ok(vec![]),
+			(ChordPolicy::Strict, _) => return Err(UnopenableError::FlagCountMismatch),
+		}
+
+		Ok(self
+			.neighbors(x, y)
+			.filter(|&(nx, ny)| self.get_board_tile(nx, ny) == Some(VisibleTile::NotVisible))
+			.collect())
+	}
+
+	/// flags or unflags a hidden tile, or chords an opened number, which is what a right click does
+	///
+	/// unlike [`BaseGameBoard::flag_tile`] right clicks on opened tiles do not error, a zero or a number with the wrong count of flags around it is a no-op returning [`None`].
+	/// Tiles of a finished game return [`UnopenableError::GameOver`]
+	fn flag_or_chord(&mut self, x: u16, y: u16) -> Result<Option<GameBoardEvent>, UnopenableError> {
+		match self
+			.get_board_tile(x, y)
+			.ok_or(UnopenableError::OutOfBounds)?
+		{
+			VisibleTile::NotVisible | VisibleTile::Flagged => self.flag_tile(x, y).map(Some),
+			VisibleTile::Visible(tile) if tile.as_count() > Some(0) => match self.open_around(x, y)
+			{
+				Ok(event) => Ok(Some(event)),
+				Err(UnopenableError::FlagCountMismatch) => Ok(None),
+				Err(err) => Err(err),
+			},
+			VisibleTile::Visible(_) => Ok(None),
+			VisibleTile::DetonatedBomb | VisibleTile::WrongFlag => Err(UnopenableError::GameOver),
+		}
+	}
+
+	/// processes a `KeyEvent` using mouse 1/2, default impl ignores other events
 	fn do_event(&mut self, k: KeyEvent) -> Result<(), UnopenableError> {
 		BaseGameBoard_do_event(self, k)
 	}
 
-	/// returns a FlatBoard of the board rendered as y/x in terms of VisibleTile's
+	/// processes a [`KeyEvent`] like [`BaseGameBoard::do_event`], and reports how many tiles it opened and the state of the game afterwards
+	///
+	/// hitting a bomb ends the game with [`BaseGameBoard::lose_game`] and is reported as [`GameState::Lost`] instead of an error
+	fn do_event_outcome(&mut self, k: KeyEvent) -> Result<EventOutcome, UnopenableError> {
+		let opened = self.opened();
+		let before = self.state();
+
+		match self.do_event(k) {
+			Ok(()) => (),
+			Err(UnopenableError::BombHit(..)) => self.lose_game(),
+			Err(e) => return Err(e),
+		}
+
+		let game_state = self.state();
+
+		if before == GameState::Playing && game_state != GameState::Playing {
+			log_debug!("game over after {k:?}: {game_state:?}");
+		}
+
+		Ok(EventOutcome {
+			opened: self.opened().saturating_sub(opened),
+			game_state,
+		})
+	}
+
+	/// returns a `FlatBoard` of the board rendered as y/x in terms of `VisibleTile`'s
 	fn render(&self) -> FlatBoard<VisibleTile> {
 		let mut board = FlatBoard::new(
 			self.get_y().into(),
@@ -133,8 +435,8 @@ pub trait BaseGameBoard: Sized {
 
 		let mut it = board.iter_backing_mut();
 
-		for y in 0..self.get_y() {
-			for x in 0..self.get_x() {
+		for y in self.y_range() {
+			for x in self.x_range() {
 				let j = it.next().unwrap();
 
 				*j = self.get_board_tile(x, y).unwrap();
@@ -144,6 +446,265 @@ pub trait BaseGameBoard: Sized {
 		board
 	}
 
+	/// yields the rendered board one row at a time in y order, without holding the whole render in memory
+	fn render_rows(&self) -> impl Iterator<Item = Vec<VisibleTile>> {
+		self.y_range().map(move |y| {
+			self.x_range()
+				.map(|x| self.get_board_tile(x, y).unwrap())
+				.collect()
+		})
+	}
+
+	/// returns a [`FlatBoard`] of the `w*h` region starting at x0/y0 rendered as y/x, where index 0/0 of the result is tile x0/y0
+	///
+	/// the region is clamped to the board bounds, so it may be smaller than `w*h` or empty
+	fn render_window(&self, x0: u16, y0: u16, w: u16, h: u16) -> FlatBoard<VisibleTile> {
+		let x1 = x0.saturating_add(w).min(self.get_x());
+		let y1 = y0.saturating_add(h).min(self.get_y());
+
+		let mut board = FlatBoard::new(
+			y1.saturating_sub(y0).into(),
+			x1.saturating_sub(x0).into(),
+			VisibleTile::NotVisible,
+		);
+
+		let mut it = board.iter_backing_mut();
+
+		for y in y0..y1 {
+			for x in x0..x1 {
+				let j = it.next().unwrap();
+
+				*j = self.get_board_tile(x, y).unwrap();
+			}
+		}
+
+		board
+	}
+
+	/// renders the board as plain ascii text, 2 columns per tile and one line per row, where covered tiles are `##`
+	fn render_plain(&self) -> String {
+		let glyphs = Glyphs::ASCII;
+		let mut out = String::new();
+
+		for row in self.render().iter() {
+			for &tile in row {
+				out.push(tile.as_glyph(&glyphs));
+				out.push(if tile == VisibleTile::NotVisible {
+					'#'
+				} else {
+					' '
+				});
+			}
+
+			out.push('\n');
+		}
+
+		out
+	}
+
+	/// renders the board as an html `<table class="board">` with one `<tr>` per row and one `<td>` per tile, styled only through classes
+	///
+	/// every cell has the `tile` class and one of `tile-hidden`, `tile-flag`, `tile-0` to `tile-8`, `tile-bomb`, `tile-detonated` or `tile-wrong-flag`,
+	/// and opened numbers also contain their digit
+	fn render_html(&self) -> String {
+		let mut out = String::from("<table class=\"board\">\n");
+
+		for row in self.render().iter() {
+			out.push_str("<tr>");
+
+			for &tile in row {
+				let (class, text) = match tile {
+					VisibleTile::NotVisible => (String::from("hidden"), ""),
+					VisibleTile::Flagged => (String::from("flag"), ""),
+					VisibleTile::DetonatedBomb => (String::from("detonated"), ""),
+					VisibleTile::WrongFlag => (String::from("wrong-flag"), ""),
+					VisibleTile::Visible(tile) => match tile.as_count() {
+						Some(0) => (String::from("0"), ""),
+						Some(count) => (count.to_string(), tile.as_str_count()),
+						None => (String::from("bomb"), ""),
+					},
+				};
+
+				out.push_str("<td class=\"tile tile-");
+				out.push_str(&class);
+				out.push_str("\">");
+				out.push_str(text);
+				out.push_str("</td>");
+			}
+
+			out.push_str("</tr>\n");
+		}
+
+		out.push_str("</table>\n");
+		out
+	}
+
+	/// counts opened, flagged and hidden tiles in a single scan of the board
+	fn tile_census(&self) -> TileCensus {
+		let mut census = TileCensus::default();
+
+		for y in self.y_range() {
+			for x in self.x_range() {
+				match self.get_board_tile(x, y).unwrap() {
+					VisibleTile::Visible(_) | VisibleTile::DetonatedBomb => census.opened += 1,
+					VisibleTile::Flagged | VisibleTile::WrongFlag => census.flagged += 1,
+					VisibleTile::NotVisible => census.hidden += 1,
+				}
+			}
+		}
+
+		census
+	}
+
+	/// returns the count of bombs around an opened tile, or [`None`] if the tile is not opened, is a bomb, or is out of bounds
+	#[inline]
+	fn opened_count_at(&self, x: u16, y: u16) -> Option<u8> {
+		match self.get_board_tile(x, y)? {
+			VisibleTile::Visible(tile) => tile.as_count(),
+			VisibleTile::NotVisible
+			| VisibleTile::Flagged
+			| VisibleTile::DetonatedBomb
+			| VisibleTile::WrongFlag => None,
+		}
+	}
+
+	/// returns how many more flags an opened number needs around it, saturating at 0 if it has too many
+	///
+	/// returns [`None`] under the same conditions as [`BaseGameBoard::opened_count_at`]
+	fn remaining_mines_around(&self, x: u16, y: u16) -> Option<MineCount> {
+		let count = MineCount::new(self.opened_count_at(x, y)?)?;
+
+		Some(count.saturating_sub(self.flags_around(x, y)))
+	}
+
+	/// returns how many of the tiles around x/y are flagged, including wrong flags, or 0 if x/y is out of bounds
+	fn flags_around(&self, x: u16, y: u16) -> u8 {
+		if !self.in_bounds(x, y) {
+			return 0;
+		}
+
+		self.neighbors(x, y)
+			.filter(|&(nx, ny)| {
+				matches!(
+					self.get_board_tile(nx, ny),
+					Some(VisibleTile::Flagged | VisibleTile::WrongFlag)
+				)
+			})
+			.map(|_| 1)
+			.sum()
+	}
+
+	/// returns how many of the tiles around x/y are neither opened nor flagged, or 0 if x/y is out of bounds
+	///
+	/// tiles on edges and corners have less than 8 tiles around them, and only in bounds tiles are counted
+	fn unopened_around(&self, x: u16, y: u16) -> u8 {
+		if !self.in_bounds(x, y) {
+			return 0;
+		}
+
+		self.neighbors(x, y)
+			.filter(|&(nx, ny)| self.get_board_tile(nx, ny) == Some(VisibleTile::NotVisible))
+			.map(|_| 1)
+			.sum()
+	}
+
+	/// returns every in bounds x/y coordinate in the 3x3 grid around a tile, excluding the tile itself
+	#[inline]
+	fn neighbors(&self, x: u16, y: u16) -> impl Iterator<Item = (u16, u16)> {
+		tiles_around(x, y, self.dimensions())
+	}
+
+	/// returns every unopened and unflagged tile that borders at least one opened number, in row major order
+	///
+	/// this is the set of tiles that the visible numbers give any information about
+	fn frontier(&self) -> impl Iterator<Item = (u16, u16)> {
+		self.y_range()
+			.flat_map(move |y| self.x_range().map(move |x| (x, y)))
+			.filter(move |&(x, y)| {
+				self.get_board_tile(x, y) == Some(VisibleTile::NotVisible)
+					&& self
+						.neighbors(x, y)
+						.any(|(nx, ny)| self.opened_count_at(nx, ny).is_some_and(|c| c > 0))
+			})
+	}
+
+	/// flags every unopened tile around each number whose unopened neighbors must all be bombs, and returns the flag events for undo
+	///
+	/// unlike the solver this only applies trivially forced flags and never opens a tile, so calling it twice in a row flags nothing the second time
+	fn toggle_flag_all_satisfied(&mut self) -> Vec<GameBoardEvent> {
+		let mut forced = BTreeSet::new();
+
+		for y in self.y_range() {
+			for x in self.x_range() {
+				let Some(count) = self.opened_count_at(x, y) else {
+					continue;
+				};
+
+				let mut flagged = 0u8;
+				let mut unopened = Vec::new();
+
+				for (nx, ny) in self.neighbors(x, y) {
+					match self.get_board_tile(nx, ny) {
+						Some(VisibleTile::NotVisible) => unopened.push((nx, ny)),
+						Some(VisibleTile::Flagged) => flagged += 1,
+						_ => (),
+					}
+				}
+
+				if count.checked_sub(flagged).map(usize::from) == Some(unopened.len()) {
+					forced.extend(unopened);
+				}
+			}
+		}
+
+		forced
+			.into_iter()
+			.filter_map(|(x, y)| self.flag_tile(x, y).ok())
+			.collect()
+	}
+
+	/// returns true if x/y is on the board, without fetching the tile
+	#[inline]
+	fn in_bounds(&self, x: u16, y: u16) -> bool {
+		let (dim_x, dim_y) = self.dimensions();
+
+		x < dim_x && y < dim_y
+	}
+
+	/// returns the row major index of x/y, or [`None`] if x/y is out of bounds
+	#[inline]
+	fn index_of(&self, x: u16, y: u16) -> Option<u32> {
+		if self.in_bounds(x, y) {
+			Some(u32::from(y) * u32::from(self.get_x()) + u32::from(x))
+		} else {
+			None
+		}
+	}
+
+	/// returns the x/y of a row major index, or [`None`] if the index is not less than the area
+	#[inline]
+	fn coordinates_of(&self, idx: u32) -> Option<(u16, u16)> {
+		if idx < self.area() {
+			let width = u32::from(self.get_x());
+
+			Some((
+				u16::try_from(idx % width).ok()?,
+				u16::try_from(idx / width).ok()?,
+			))
+		} else {
+			None
+		}
+	}
+
+	/// opens the tile at a row major index
+	fn open_index(&mut self, idx: u32) -> Result<GameBoardEvent, UnopenableError> {
+		let (x, y) = self
+			.coordinates_of(idx)
+			.ok_or(UnopenableError::OutOfBounds)?;
+
+		self.open_tile(x, y)
+	}
+
 	/// returns how many tiles are left to open
 	#[inline]
 	fn tiles_left(&self) -> u32 {
@@ -174,9 +735,61 @@ pub trait BaseGameBoard: Sized {
 		self.dimensions().1
 	}
 
+	/// returns the range of every x coordinate on the board
+	#[inline]
+	fn x_range(&self) -> Range<u16> {
+		0..self.get_x()
+	}
+
+	/// returns the range of every y coordinate on the board
+	#[inline]
+	fn y_range(&self) -> Range<u16> {
+		0..self.get_y()
+	}
+
+	/// returns the mark a player annotated x/y with, which has no effect on play, the default impl has no annotations and returns [`None`]
+	#[inline]
+	fn annotation(&self, _x: u16, _y: u16) -> Option<char> {
+		None
+	}
+
+	/// returns how [`BaseGameBoard::open_around`] treats a number whose flag count does not match it, the default impl is [`ChordPolicy::Strict`]
+	#[inline]
+	fn chord_policy(&self) -> ChordPolicy {
+		ChordPolicy::Strict
+	}
+
+	/// returns the 3BV of the hidden layout, the least amount of clicks that clear the board, or [`None`] if the board can not compute it
+	///
+	/// this is meant for end of game stats, the default impl returns [`None`]
+	#[inline]
+	fn layout_3bv(&self) -> Option<u32> {
+		None
+	}
+
+	/// captures the complete state of the board, the hidden layout, visibility, counters and game status,
+	/// so a move can be tried and rolled back with [`BaseGameBoard::restore`] without undoing it
+	///
+	/// the default impl captures nothing, and restoring it returns [`RestoreError::Unsupported`]
+	fn snapshot(&self) -> BoardSnapshot {
+		BoardSnapshot::new(())
+	}
+
+	/// returns the board to the state captured by [`BaseGameBoard::snapshot`], leaving it unchanged on error
+	///
+	/// snapshots of a different kind of board return [`RestoreError::WrongBoard`], the default impl returns [`RestoreError::Unsupported`]
+	fn restore(&mut self, _snapshot: &BoardSnapshot) -> Result<(), RestoreError> {
+		Err(RestoreError::Unsupported)
+	}
+
 	/// returns the bomb density as a float in the range \[0,1\]
+	///
+	/// a board with no area has a density of 0 rather than NaN
 	#[inline]
 	fn bomb_density(&self) -> f64 {
-		f64::from(self.bomb_count()) / f64::from(self.area())
+		match self.area() {
+			0 => 0.,
+			area => f64::from(self.bomb_count()) / f64::from(area),
+		}
 	}
 }
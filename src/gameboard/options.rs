@@ -0,0 +1,109 @@
+//! rule options that change how a [`GameBoard`][super::GameBoard] plays
+
+/// the condition that has to be met for [`BaseGameBoard::win_game`][super::BaseGameBoard::win_game] to succeed
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum WinCondition {
+	/// every tile that is not a bomb is open
+	#[default]
+	OpenAllSafe,
+	/// every bomb is flagged, and no other tile is
+	FlagAllMines,
+	/// either of [`WinCondition::OpenAllSafe`] or [`WinCondition::FlagAllMines`]
+	Either,
+}
+
+/// how [`BaseGameBoard::open_around`][super::BaseGameBoard::open_around] treats a number whose flag count does not match it
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum ChordPolicy {
+	/// any mismatch is a [`UnopenableError::FlagCountMismatch`][super::UnopenableError::FlagCountMismatch]
+	#[default]
+	Strict,
+	/// fewer flags than the number opens nothing and returns an empty event,
+	/// more flags than the number still opens the rest, so only a bomb under them is an error
+	Lenient,
+}
+
+/// the set of rule options of a [`GameBoard`][super::GameBoard], where the default is classic minesweeper
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+// every switch is an independent rule, so they are not merged into a state enum
+#[allow(clippy::struct_excessive_bools)]
+pub struct BoardOptions {
+	pub win_condition: WinCondition,
+	/// refuse to place more flags than there are bombs, unflagging is always allowed
+	pub limit_flags: bool,
+	/// open every tile around an opened zero, which is on by default.
+	/// When off, opening a zero only opens that tile
+	pub auto_cascade: bool,
+	/// return [`GameBoardEvent::OpenSeed`][super::GameBoardEvent::OpenSeed] from `open_tile` instead of listing every opened tile,
+	/// which saves memory on large cascades at the cost of recomputing the cascade on undo
+	pub compact_undo: bool,
+	/// after every opening, flag the covered tiles around a number that can only be bombs,
+	/// which is when the number has exactly as many covered tiles around it as flags left to place
+	pub auto_flag_forced: bool,
+	pub chord_policy: ChordPolicy,
+}
+
+impl Default for BoardOptions {
+	fn default() -> Self {
+		Self {
+			win_condition: WinCondition::default(),
+			limit_flags: false,
+			auto_cascade: true,
+			compact_undo: false,
+			auto_flag_forced: false,
+			chord_policy: ChordPolicy::default(),
+		}
+	}
+}
+
+/// a board size and bomb count to start a game with
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Difficulty {
+	/// 9x9 with 10 bombs
+	Beginner,
+	/// 16x16 with 40 bombs
+	#[default]
+	Intermediate,
+	/// 30x16 with 99 bombs
+	Expert,
+	Custom {
+		x: u16,
+		y: u16,
+		bombs: u32,
+	},
+}
+
+impl Difficulty {
+	/// returns the x/y dimensions and bomb count of this difficulty
+	#[must_use]
+	pub const fn config(self) -> (u16, u16, u32) {
+		match self {
+			Self::Beginner => (9, 9, 10),
+			Self::Intermediate => (16, 16, 40),
+			Self::Expert => (30, 16, 99),
+			Self::Custom { x, y, bombs } => (x, y, bombs),
+		}
+	}
+}
+
+/// how a bomb density is rounded to a whole bomb count
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum RoundMode {
+	/// rounds to the nearest count, with halves rounding away from zero
+	#[default]
+	Nearest,
+	Floor,
+	Ceil,
+}
+
+impl RoundMode {
+	/// rounds v with this mode
+	#[must_use]
+	pub const fn apply(self, v: f64) -> f64 {
+		match self {
+			Self::Nearest => v.round(),
+			Self::Floor => v.floor(),
+			Self::Ceil => v.ceil(),
+		}
+	}
+}
@@ -0,0 +1,1666 @@
+use super::*;
+
+/// parses a layout in the format of [`GameBoard::from_board_format`]
+fn board(layout: &str) -> GameBoard {
+	GameBoard::from_board_format(layout).unwrap()
+}
+
+#[test]
+fn opened_count_at_reads_opened_numbers_only() {
+	let mut gb = board("100\n000\n000");
+	gb.open_tile(1, 0).unwrap();
+
+	assert_eq!(gb.opened_count_at(1, 0), Some(1));
+	// covered tiles and bombs have no readable count
+	assert_eq!(gb.opened_count_at(2, 2), None);
+	assert_eq!(gb.opened_count_at(0, 0), None);
+	assert_eq!(gb.opened_count_at(3, 0), None);
+
+	gb.lose_game();
+	assert_eq!(gb.opened_count_at(0, 0), None);
+}
+
+#[test]
+fn display_with_uses_the_style_per_tile() {
+	let mut gb = board("100\n000\n000");
+	gb.open_tile(2, 2).unwrap();
+
+	let style = |t: VisibleTile| match t {
+		VisibleTile::NotVisible => String::from("#"),
+		VisibleTile::Visible(t) => t.as_count().unwrap().to_string(),
+		_ => String::from("?"),
+	};
+
+	assert_eq!(gb.display_with(&style).to_string(), "#10\n110\n000\n");
+	// the default impl is unchanged and still colors every opened tile
+	assert!(gb.to_string().contains("\u{1b}[0m"));
+}
+
+#[test]
+fn bomb_density_of_zero_area_is_zero() {
+	use crate::mock::MockGameBoard;
+
+	let empty = MockGameBoard::new(FlatBoard::new(0, 0, VisibleTile::NotVisible), 0);
+	assert_eq!(empty.area(), 0);
+	assert!(empty.bomb_density().abs() < f64::EPSILON);
+
+	// a single tile is the smallest board with live area
+	let single = GameBoard::with_bombs(1, 1, &[(0, 0)]).unwrap();
+	assert!((single.bomb_density() - 1.0).abs() < f64::EPSILON);
+}
+
+#[test]
+fn frontier_lists_unknowns_next_to_numbers() {
+	let mut gb = board("1000\n0000\n0000\n0001");
+	gb.options_mut().auto_cascade = false;
+
+	for (x, y) in [(1, 0), (0, 1), (2, 2)] {
+		gb.open_tile(x, y).unwrap();
+	}
+	gb.flag_tile(3, 3).unwrap();
+
+	// 3/0 and 0/3 border no opened number, and the flagged 3/3 is not an unknown
+	assert_eq!(
+		gb.frontier().collect::<Vec<_>>(),
+		[
+			(0, 0),
+			(2, 0),
+			(1, 1),
+			(2, 1),
+			(3, 1),
+			(0, 2),
+			(1, 2),
+			(3, 2),
+			(1, 3),
+			(2, 3)
+		]
+	);
+}
+
+#[test]
+fn do_event_outcome_reports_wins_and_losses() {
+	let mut gb = board("100\n000\n001");
+
+	let outcome = gb.do_event_outcome(KeyEvent::Mouse1(2, 0)).unwrap();
+	assert_eq!(outcome.opened, 4);
+	assert_eq!(outcome.game_state, GameState::Playing);
+
+	let outcome = gb.do_event_outcome(KeyEvent::Mouse1(0, 2)).unwrap();
+	assert_eq!(outcome.opened, 3);
+	assert_eq!(outcome.game_state, GameState::Won);
+
+	// a bomb hit loses the game instead of being an error
+	let mut gb = board("100\n000\n001");
+
+	let outcome = gb.do_event_outcome(KeyEvent::Mouse1(0, 0)).unwrap();
+	assert_eq!(outcome.opened, 0);
+	assert_eq!(outcome.game_state, GameState::Lost);
+}
+
+#[test]
+fn glyphs_replace_every_symbol() {
+	let custom = Glyphs {
+		flag: 'f',
+		bomb: 'b',
+		mine_hit: 'h',
+		wrong_flag: 'w',
+		question: 'q',
+	};
+
+	assert_eq!(VisibleTile::Flagged.as_glyph(&Glyphs::DEFAULT), '\u{2691}');
+	assert_eq!(VisibleTile::Flagged.as_glyph(&Glyphs::ASCII), 'F');
+	assert_eq!(
+		VisibleTile::Visible(Tile::Bomb).as_glyph(&Glyphs::ASCII),
+		'*'
+	);
+
+	let tiles = [
+		VisibleTile::Flagged,
+		VisibleTile::Visible(Tile::Bomb),
+		VisibleTile::DetonatedBomb,
+		VisibleTile::WrongFlag,
+	];
+
+	assert_eq!(tiles.map(|t| t.as_glyph(&custom)), ['f', 'b', 'h', 'w']);
+}
+
+#[test]
+fn loss_reports_exactly_one_detonated_bomb() {
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+
+	let err = gb.open_tile(2, 0).unwrap_err();
+	assert!(matches!(err, UnopenableError::BombHit(2, 0)));
+	gb.lose_game();
+
+	let rendered = gb.render();
+	let detonated = rendered
+		.iter_backing()
+		.filter(|&&t| t == VisibleTile::DetonatedBomb)
+		.count();
+
+	assert_eq!(detonated, 1);
+	assert_eq!(rendered[0][2], VisibleTile::DetonatedBomb);
+	assert_eq!(gb.get_board_tile(2, 0), Some(VisibleTile::DetonatedBomb));
+	assert_eq!(rendered[0][0], VisibleTile::Visible(Tile::Bomb));
+}
+
+#[test]
+fn loss_reveals_wrong_flags() {
+	let mut gb = board("101\n000\n000");
+	gb.options_mut().auto_cascade = false;
+
+	gb.flag_tile(0, 0).unwrap();
+	gb.flag_tile(1, 1).unwrap();
+
+	// flags stay hidden as flags until the game is lost
+	assert_eq!(gb.get_board_tile(1, 1), Some(VisibleTile::Flagged));
+
+	gb.open_tile(2, 0).unwrap_err();
+	let reveal = gb.finalize_loss();
+
+	assert_eq!(gb.get_board_tile(1, 1), Some(VisibleTile::WrongFlag));
+	assert_eq!(reveal.wrong_flags, [(1, 1)]);
+	assert_eq!(reveal.bombs, [(0, 0)]);
+
+	// the correct flag is kept instead of being revealed as a bare bomb
+	assert_eq!(gb.get_board_tile(0, 0), Some(VisibleTile::Flagged));
+	assert_eq!(gb.flagged(), 2);
+}
+
+#[test]
+fn toggle_flag_all_satisfied_is_idempotent() {
+	// opening without a cascade keeps the game going after the one bomb is forced
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_cascade = false;
+
+	for (x, y) in [(1, 0), (2, 0), (0, 1), (1, 1), (2, 1)] {
+		gb.open_tile(x, y).unwrap();
+	}
+
+	assert_eq!(
+		gb.toggle_flag_all_satisfied(),
+		[GameBoardEvent::flag_tile(0, 0)]
+	);
+	assert_eq!(gb.get_board_tile(0, 0), Some(VisibleTile::Flagged));
+
+	assert!(gb.toggle_flag_all_satisfied().is_empty());
+	assert_eq!(gb.flagged(), 1);
+}
+
+#[test]
+fn toggle_flag_all_satisfied_skips_unforced_numbers() {
+	// every number of the 1 2 1 row has more unknowns than bombs left
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+
+	assert!(gb.toggle_flag_all_satisfied().is_empty());
+}
+
+#[test]
+fn new_solvable_counted_reports_attempts() {
+	let (mut gb, attempts) = GameBoard::new_solvable_counted(9, 9, 10, 4, 4).unwrap();
+
+	assert!((1..=MAX_SOLVABLE_ATTEMPTS).contains(&attempts));
+
+	gb.open_tile(4, 4).unwrap();
+	assert!(solve_without_guessing(&mut gb));
+}
+
+#[test]
+fn open_tile_bfs_groups_tiles_by_distance() {
+	let mut gb = board("0000\n0000\n0000\n0001");
+
+	let rings = gb.open_tile_bfs(0, 0).unwrap();
+
+	assert_eq!(rings[0], [(0, 0)]);
+	assert_eq!(rings.iter().map(Vec::len).sum::<usize>(), 15);
+	assert_eq!(gb.opened(), 15);
+
+	for (distance, ring) in rings.iter().enumerate() {
+		for &(x, y) in ring {
+			assert_eq!(usize::from(x.max(y)), distance);
+		}
+	}
+
+	// flattening the rings gives an undoable event
+	let event = GameBoardEvent::from(rings.concat());
+	gb.undo_move(&event).unwrap();
+	assert_eq!(gb.opened(), 0);
+}
+
+#[test]
+fn validate_board_checks_clearings_uniformly() {
+	assert!(GameBoard::validate_board(9, 9, 10, None).is_ok());
+	assert!(GameBoard::validate_board(9, 9, 10, (8, 8)).is_ok());
+
+	for clearing in [(9, 0), (0, 9), (9, 9)] {
+		assert!(matches!(
+			GameBoard::validate_board(9, 9, 10, clearing),
+			Err(NewBoardError::SizeConstraintOverflow)
+		));
+		assert!(matches!(
+			GameBoard::with_clearing(9, 9, 10, clearing.0, clearing.1),
+			Err(NewBoardError::SizeConstraintOverflow)
+		));
+		assert!(matches!(
+			GameBoard::with_clearings(9, 9, 10, &[(0, 0), clearing]),
+			Err(NewBoardError::SizeConstraintOverflow)
+		));
+	}
+
+	// without a clearing every tile but one may be a bomb, with one the zone must stay free
+	assert!(GameBoard::validate_board(9, 9, 80, None).is_ok());
+	assert!(matches!(
+		GameBoard::validate_board(9, 9, 80, (4, 4)),
+		Err(NewBoardError::BombOverflow)
+	));
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn is_safe_reads_the_hidden_layout() {
+	let gb = board("10\n00");
+
+	assert!(!gb.is_safe(0, 0));
+	assert!(gb.is_safe(1, 0));
+	assert!(!gb.is_safe(2, 0));
+}
+
+#[test]
+fn diff_lists_changed_tiles() {
+	let mut gb = board("10\n00");
+	let before = gb.render();
+
+	gb.flag_tile(0, 0).unwrap();
+
+	assert_eq!(
+		diff(&before, &gb.render()).unwrap(),
+		[(0, 0, VisibleTile::Flagged)]
+	);
+	assert!(diff(&before, &before).unwrap().is_empty());
+
+	let other = board("100\n000").render();
+	assert!(matches!(
+		diff(&before, &other),
+		Err(DiffError::DimensionMismatch)
+	));
+}
+
+#[test]
+fn open_index_round_trips_index_of() {
+	let mut gb = board("100\n000");
+	gb.options_mut().auto_cascade = false;
+
+	assert_eq!(gb.index_of(2, 1), Some(5));
+	assert_eq!(gb.coordinates_of(5), Some((2, 1)));
+	assert_eq!(gb.index_of(3, 0), None);
+	assert_eq!(gb.coordinates_of(6), None);
+
+	assert_eq!(gb.open_index(5).unwrap(), vec![(2, 1)].into());
+	assert!(matches!(
+		gb.open_index(6),
+		Err(UnopenableError::OutOfBounds)
+	));
+}
+
+#[test]
+fn visibility_snapshot_round_trips() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+
+	let mask = gb.visibility_snapshot();
+	let mut restored = gb.restart_same_seed();
+	restored.apply_visibility_snapshot(&mask).unwrap();
+
+	assert_eq!(restored.render(), gb.render());
+	assert_eq!(restored.opened(), gb.opened());
+	assert_eq!(restored.flagged(), 1);
+	assert_eq!(restored.state(), GameState::Playing);
+	assert_eq!(restored.visibility_snapshot(), mask);
+
+	let other = board("1000\n0000");
+	assert!(matches!(
+		gb.apply_visibility_snapshot(&other.visibility_snapshot()),
+		Err(RestoreError::DimensionMismatch)
+	));
+}
+
+#[test]
+fn visibility_snapshot_restores_losses() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.open_tile(0, 0).unwrap_err();
+	gb.lose_game();
+
+	let mut restored = gb.restart_same_seed();
+	restored
+		.apply_visibility_snapshot(&gb.visibility_snapshot())
+		.unwrap();
+
+	assert_eq!(restored.state(), GameState::Lost);
+	assert!(matches!(
+		restored.open_tile(0, 2),
+		Err(UnopenableError::GameOver)
+	));
+
+	// applying a playing snapshot again resumes the game
+	let fresh = gb.restart_same_seed().visibility_snapshot();
+	restored.apply_visibility_snapshot(&fresh).unwrap();
+	assert_eq!(restored.state(), GameState::Playing);
+}
+
+#[test]
+fn visibility_snapshot_rejects_partly_revealed_bombs() {
+	let mut lost = board("10\n01");
+	lost.lose_game();
+
+	// a board with a different layout sees only one of its bombs revealed
+	let mut gb = board("10\n10");
+	assert!(matches!(
+		gb.apply_visibility_snapshot(&lost.visibility_snapshot()),
+		Err(RestoreError::Corrupted)
+	));
+	assert_eq!(gb.state(), GameState::Playing);
+	assert_eq!(gb.opened(), 0);
+}
+
+#[test]
+fn moves_are_rejected_once_the_game_is_won() {
+	let mut open = board("100\n000\n000");
+	open.open_tile(2, 2).unwrap();
+	assert_eq!(open.state(), GameState::Won);
+	assert!(matches!(
+		open.flag_tile(0, 0),
+		Err(UnopenableError::GameOver)
+	));
+
+	for condition in [WinCondition::FlagAllMines, WinCondition::Either] {
+		let mut gb = board("100\n000\n000");
+		gb.options_mut().win_condition = condition;
+
+		gb.flag_tile(0, 0).unwrap();
+		assert_eq!(gb.state(), GameState::Won);
+
+		assert!(matches!(gb.open_tile(2, 2), Err(UnopenableError::GameOver)));
+		assert!(matches!(gb.flag_tile(0, 0), Err(UnopenableError::GameOver)));
+		assert_eq!(gb.tiles_left(), 8);
+	}
+}
+
+#[test]
+fn win_conditions_are_met_at_different_points() {
+	let opened = |condition| {
+		let mut gb = board("100\n000\n000");
+		gb.options_mut().win_condition = condition;
+		gb.open_tile(2, 2).unwrap();
+		gb.state()
+	};
+
+	assert_eq!(opened(WinCondition::OpenAllSafe), GameState::Won);
+	assert_eq!(opened(WinCondition::FlagAllMines), GameState::Playing);
+	assert_eq!(opened(WinCondition::Either), GameState::Won);
+
+	// a wrong flag next to the right one keeps a flag win from counting
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().win_condition = WinCondition::FlagAllMines;
+	gb.flag_tile(1, 1).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+	assert_eq!(gb.state(), GameState::Playing);
+
+	gb.flag_tile(1, 1).unwrap();
+	assert_eq!(gb.state(), GameState::Won);
+}
+
+#[test]
+fn render_window_clamps_at_the_bottom_right_corner() {
+	let mut gb = board("010\n000\n000");
+	gb.open_tile(2, 2).unwrap();
+
+	let window = gb.render_window(1, 1, 5, 5);
+	assert_eq!(window.dimensions(), (2, 2));
+
+	for ((y, x), &tile) in window.enumerate_2d() {
+		let (x, y) = narrow_xy(x + 1, y + 1);
+		assert_eq!(Some(tile), gb.get_board_tile(x, y));
+	}
+
+	assert_eq!(gb.render_window(3, 0, 2, 2).dimensions().1, 0);
+}
+
+#[test]
+fn seed_is_kept_by_copies_and_snapshots() {
+	let mut gb = GameBoard::new_seeded(9, 9, 10, 7).unwrap();
+	assert_eq!(gb.seed(), Some(7));
+	assert_eq!(gb.restart_same_seed().seed(), Some(7));
+
+	let snapshot = gb.snapshot();
+	gb = GameBoard::new(9, 9, 10).unwrap();
+	assert_eq!(gb.seed(), None);
+
+	gb.restore(&snapshot).unwrap();
+	assert_eq!(gb.seed(), Some(7));
+}
+
+#[test]
+fn with_bombs_places_exactly_the_given_bombs() {
+	let mut gb = GameBoard::with_bombs(3, 3, &[(0, 0), (2, 1)]).unwrap();
+
+	assert_eq!(gb.bomb_count(), 2);
+	assert_eq!(gb.to_board_format(), "100\n001\n000\n");
+
+	gb.open_tile(1, 0).unwrap();
+	assert_eq!(gb.opened_count_at(1, 0), Some(2));
+
+	assert!(matches!(
+		GameBoard::with_bombs(3, 3, &[(0, 0), (0, 0)]),
+		Err(NewBoardError::DuplicateBomb)
+	));
+	assert!(matches!(
+		GameBoard::with_bombs(3, 3, &[(3, 0)]),
+		Err(NewBoardError::BombOutOfBounds)
+	));
+}
+
+#[test]
+fn tile_census_sums_to_the_area() {
+	let mut gb = board("100\n000\n001");
+	let total = |c: TileCensus| c.opened + c.flagged + c.hidden;
+
+	assert_eq!(total(gb.tile_census()), gb.area());
+
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+	gb.flag_tile(0, 2).unwrap();
+
+	let census = gb.tile_census();
+	assert_eq!((census.opened, census.flagged), (4, 2));
+	assert_eq!(census.opened, gb.opened());
+	assert_eq!(total(census), gb.area());
+}
+
+#[test]
+fn finalize_loss_summarizes_the_reveal() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 1).unwrap();
+
+	assert!(matches!(
+		gb.open_tile(2, 2),
+		Err(UnopenableError::BombHit(2, 2))
+	));
+	let reveal = gb.finalize_loss();
+
+	assert_eq!(gb.state(), GameState::Lost);
+	assert_eq!(reveal.detonated, Some((2, 2)));
+	assert_eq!(reveal.bombs, [(0, 0)]);
+	assert_eq!(reveal.wrong_flags, [(0, 1)]);
+	assert_eq!(
+		gb.get_board_tile(0, 0),
+		Some(VisibleTile::Visible(Tile::Bomb))
+	);
+
+	// a second call returns the same summary
+	assert_eq!(gb.finalize_loss(), reveal);
+}
+
+#[test]
+fn finalize_loss_leaves_a_won_game_won() {
+	let mut gb = board("100\n000\n000");
+	gb.open_tile(2, 2).unwrap();
+	assert_eq!(gb.state(), GameState::Won);
+
+	assert_eq!(gb.finalize_loss(), LossReveal::default());
+	assert_eq!(gb.state(), GameState::Won);
+	assert_eq!(gb.get_board_tile(0, 0), Some(VisibleTile::NotVisible));
+}
+
+#[test]
+fn bomb_hits_lose_the_game_at_the_detonated_tile() {
+	let mut gb = board("100\n000\n001");
+
+	assert!(matches!(
+		gb.open_tile(2, 2),
+		Err(UnopenableError::BombHit(2, 2))
+	));
+	assert_eq!(gb.state(), GameState::Lost);
+	assert_eq!(gb.get_board_tile(2, 2), Some(VisibleTile::DetonatedBomb));
+	assert_eq!(gb.finalize_loss().detonated, Some((2, 2)));
+
+	// a wrong flag lets a chord reach the bomb next to it
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 1).unwrap();
+
+	assert!(matches!(
+		gb.open_around(1, 0),
+		Err(UnopenableError::BombHit(0, 0))
+	));
+	assert_eq!(gb.state(), GameState::Lost);
+	assert_eq!(gb.finalize_loss().detonated, Some((0, 0)));
+}
+
+#[test]
+fn open_around_needs_an_opened_number() {
+	let mut gb = board("100\n000\n001");
+
+	// covered tiles are not chorded, whether a bomb or a number is under them
+	assert!(matches!(
+		gb.open_around(0, 0),
+		Err(UnopenableError::NotANumber)
+	));
+	assert!(matches!(
+		gb.open_around(1, 1),
+		Err(UnopenableError::NotANumber)
+	));
+	assert_eq!(gb.state(), GameState::Playing);
+
+	gb.flag_tile(0, 0).unwrap();
+	assert!(matches!(
+		gb.open_around(0, 0),
+		Err(UnopenableError::NotANumber)
+	));
+}
+
+#[test]
+fn flag_limit_stops_at_the_bomb_count() {
+	let mut gb = board("100\n000\n001");
+	gb.options_mut().limit_flags = true;
+
+	gb.flag_tile(1, 1).unwrap();
+	gb.flag_tile(2, 1).unwrap();
+	assert!(matches!(
+		gb.flag_tile(0, 0),
+		Err(UnopenableError::FlagLimitReached)
+	));
+	assert_eq!(gb.flagged(), 2);
+
+	// unflagging is always allowed and frees a flag
+	gb.flag_tile(1, 1).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+	assert_eq!(gb.flagged(), 2);
+
+	gb.options_mut().limit_flags = false;
+	gb.flag_tile(1, 1).unwrap();
+	assert_eq!(gb.flagged(), 3);
+}
+
+#[test]
+fn remaining_mines_around_subtracts_flags() {
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+
+	let remaining = |gb: &GameBoard, x, y| gb.remaining_mines_around(x, y).map(MineCount::get);
+
+	assert_eq!(remaining(&gb, 1, 1), Some(2));
+	gb.flag_tile(0, 0).unwrap();
+	assert_eq!(remaining(&gb, 1, 1), Some(1));
+	assert_eq!(remaining(&gb, 0, 1), Some(0));
+
+	// covered tiles, bombs and tiles off the board have no count
+	assert_eq!(remaining(&gb, 1, 0), None);
+	assert_eq!(remaining(&gb, 2, 0), None);
+	assert_eq!(remaining(&gb, 3, 0), None);
+}
+
+#[test]
+fn with_clearings_reserves_every_zone_once() {
+	let zones_clear = |gb: &GameBoard, clears: &[(u16, u16)]| {
+		clears.iter().all(|&(cx, cy)| {
+			let mut zone = gb.normalize_around_3x3(cx, cy);
+			zone.push(widen_xy(cx, cy));
+
+			zone.into_iter()
+				.all(|(x, y)| !gb.board[y][x].tile.is_bomb())
+		})
+	};
+
+	// disjoint zones reserve 18 tiles, which is exactly the room left by 63 bombs
+	let disjoint = [(1, 1), (7, 7)];
+	let gb = GameBoard::with_clearings(9, 9, 63, &disjoint).unwrap();
+	assert!(zones_clear(&gb, &disjoint));
+	assert_eq!(gb.clearing(), Some((1, 1)));
+	assert!(matches!(
+		GameBoard::with_clearings(9, 9, 64, &disjoint),
+		Err(NewBoardError::BombOverflow)
+	));
+
+	// overlapping zones share 6 tiles, leaving room for 69 bombs
+	let overlapping = [(1, 1), (2, 1)];
+	let gb = GameBoard::with_clearings(9, 9, 69, &overlapping).unwrap();
+	assert!(zones_clear(&gb, &overlapping));
+	assert!(matches!(
+		GameBoard::with_clearings(9, 9, 70, &overlapping),
+		Err(NewBoardError::BombOverflow)
+	));
+
+	assert!(matches!(
+		GameBoard::with_clearings(9, 9, 10, &[(9, 0)]),
+		Err(NewBoardError::SizeConstraintOverflow)
+	));
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn count_grid_matches_recounted_neighbors() {
+	let gb = GameBoard::new_seeded(16, 16, 40, 3).unwrap();
+
+	for ((y, x), &count) in gb.count_grid().enumerate_2d() {
+		let (x, y) = narrow_xy(x, y);
+
+		if gb.is_safe(x, y) {
+			assert_eq!(count, gb.computed_bombs_around_tile(x, y).get());
+		} else {
+			assert_eq!(count, GameBoard::COUNT_GRID_BOMB);
+		}
+	}
+}
+
+#[test]
+fn open_or_chord_picks_the_move_by_visible_tile() {
+	let mut gb = board("100\n000\n001");
+
+	gb.open_or_chord(2, 0).unwrap();
+	assert_eq!(gb.opened(), 4);
+	gb.flag_tile(0, 0).unwrap();
+
+	assert!(matches!(
+		gb.open_or_chord(0, 0),
+		Err(UnopenableError::FlaggedTile)
+	));
+	assert!(matches!(
+		gb.open_or_chord(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+
+	gb.open_or_chord(1, 0).unwrap();
+	assert_eq!(gb.opened_count_at(0, 1), Some(1));
+}
+
+#[test]
+fn ranges_cover_each_axis() {
+	let gb = board("0000\n0000\n0001");
+
+	assert_eq!(gb.x_range(), 0..4);
+	assert_eq!(gb.y_range(), 0..3);
+	assert_eq!(gb.x_range().len() * gb.y_range().len(), 12);
+}
+
+#[test]
+fn new_with_3bv_range_is_reproducible_from_its_seed() {
+	let gb = GameBoard::new_with_3bv_range(9, 9, 10, 10, 40, 5).unwrap();
+	assert!((10..=40).contains(&gb.board_3bv()));
+
+	let again = GameBoard::new_seeded(9, 9, 10, gb.seed().unwrap()).unwrap();
+	assert_eq!(again.to_board_format(), gb.to_board_format());
+
+	// a board with a safe tile always needs at least one click
+	assert!(matches!(
+		GameBoard::new_with_3bv_range(3, 3, 1, 0, 0, 5),
+		Err(NewBoardError::AttemptsExhausted)
+	));
+}
+
+#[test]
+fn failed_undo_leaves_the_board_unchanged() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	let before = gb.render();
+
+	// the second cell was never opened, so nothing may be closed
+	let event = GameBoardEvent::from(vec![(2, 0), (0, 2), (1, 0)]);
+	assert!(matches!(
+		gb.undo_move(&event),
+		Err(UndoError::AlreadyClosed)
+	));
+	assert_eq!(gb.render(), before);
+	assert_eq!(gb.opened(), 4);
+
+	let off_board = GameBoardEvent::from(vec![(2, 0), (3, 0)]);
+	assert!(matches!(
+		gb.undo_move(&off_board),
+		Err(UndoError::OutOfBounds)
+	));
+	assert_eq!(gb.render(), before);
+	assert_eq!(gb.opened(), 4);
+}
+
+#[test]
+fn resize_preserving_density_keeps_the_density() {
+	let gb = GameBoard::new_seeded(16, 16, 40, 1).unwrap();
+
+	for (x, y) in [(30, 16), (9, 9), (7, 3)] {
+		let resized = gb.resize_preserving_density(x, y, 2).unwrap();
+		let half_a_bomb = 0.5 / f64::from(resized.area());
+
+		assert_eq!(resized.dimensions(), (x, y));
+		assert!((resized.bomb_density() - gb.bomb_density()).abs() <= half_a_bomb);
+	}
+
+	assert_eq!(
+		gb.resize_preserving_density(10, 10, 2)
+			.unwrap()
+			.bomb_count(),
+		16
+	);
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn neighbor_bomb_count_reads_the_stored_count() {
+	let gb = board("100\n000\n001");
+	let count = |x, y| gb.neighbor_bomb_count(x, y).map(MineCount::get);
+
+	// covered tiles are read too, this is the hidden layout
+	assert_eq!(count(1, 1), Some(2));
+	assert_eq!(count(2, 0), Some(0));
+	assert_eq!(count(0, 0), None);
+	assert_eq!(count(3, 0), None);
+}
+
+#[test]
+fn with_clearing_min_opening_opens_at_least_min_tiles() {
+	let mut gb = GameBoard::with_clearing_min_opening(16, 16, 40, 8, 8, 30, 9).unwrap();
+	gb.open_tile(8, 8).unwrap();
+	assert!(gb.opened() >= 30);
+
+	// a 3x4 board with 3 bombs only has 9 safe tiles to open
+	assert!(matches!(
+		GameBoard::with_clearing_min_opening(3, 4, 3, 1, 1, 10, 9),
+		Err(NewBoardError::AttemptsExhausted)
+	));
+}
+
+#[test]
+fn flags_around_counts_flagged_neighbors() {
+	let mut gb = board("100\n000\n001");
+
+	gb.flag_tile(0, 0).unwrap();
+	gb.flag_tile(2, 1).unwrap();
+
+	assert_eq!(gb.flags_around(1, 1), 2);
+	assert_eq!(gb.flags_around(1, 0), 2);
+	assert_eq!(gb.flags_around(0, 2), 0);
+
+	// the center is not its own neighbor, and off board centers have none
+	assert_eq!(gb.flags_around(0, 0), 0);
+	assert_eq!(gb.flags_around(3, 3), 0);
+}
+
+#[test]
+fn density_rounding_modes_at_half_a_bomb() {
+	// 0.125 of 4x3 is exactly 1.5 bombs
+	let bombs = |mode| {
+		GameBoard::with_density_rounding(4, 3, 0.125, mode)
+			.unwrap()
+			.bomb_count()
+	};
+
+	assert_eq!(bombs(RoundMode::Nearest), 2);
+	assert_eq!(bombs(RoundMode::Floor), 1);
+	assert_eq!(bombs(RoundMode::Ceil), 2);
+	assert_eq!(
+		GameBoard::with_density(4, 3, 0.125).unwrap().bomb_count(),
+		2
+	);
+
+	// the extremes stay in bounds either way
+	assert_eq!(
+		GameBoard::with_density_rounding(4, 3, 1.0, RoundMode::Ceil)
+			.unwrap()
+			.bomb_count(),
+		12
+	);
+}
+
+#[test]
+fn with_density_rejects_invalid_densities() {
+	for density in [f64::NAN, -0.1, 1.1, f64::INFINITY] {
+		assert!(
+			matches!(
+				GameBoard::with_density(9, 9, density),
+				Err(NewBoardError::InvalidDensity)
+			),
+			"{density}"
+		);
+	}
+
+	assert_eq!(GameBoard::with_density(9, 9, 0.0).unwrap().bomb_count(), 0);
+}
+
+#[test]
+fn render_rows_concatenate_to_render() {
+	let mut gb = board("0100\n0000\n0001");
+	gb.open_tile(0, 2).unwrap();
+	gb.flag_tile(1, 0).unwrap();
+
+	let rows: Vec<_> = gb.render_rows().collect();
+	let rendered = gb.render();
+
+	assert_eq!(rows.len(), 3);
+	assert!(rows
+		.iter()
+		.zip(rendered.iter())
+		.all(|(row, r)| row[..] == r[..]));
+}
+
+#[test]
+fn opening_a_zero_without_cascade_opens_one_tile() {
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_cascade = false;
+
+	assert_eq!(
+		gb.open_tile(2, 2).unwrap(),
+		GameBoardEvent::from(vec![(2, 2)])
+	);
+	assert_eq!(gb.opened(), 1);
+
+	// chording the zero opens its neighbors, but not past the zeros among them
+	gb.open_around(2, 2).unwrap();
+	assert_eq!(gb.opened(), 4);
+
+	gb.options_mut().auto_cascade = true;
+	gb.open_tile(0, 2).unwrap();
+	assert_eq!(gb.state(), GameState::Won);
+}
+
+#[test]
+fn compact_and_listed_opens_undo_to_the_same_board() {
+	// a cascade that stops at numbers, and one that wraps around a bomb
+	let opens = [
+		("100\n000\n001", (2, 0)),
+		("0000\n0100\n0000\n0000", (3, 3)),
+	];
+
+	for (layout, (x, y)) in opens {
+		let fresh = board(layout).render();
+
+		for compact in [false, true] {
+			let mut gb = board(layout);
+			gb.options_mut().compact_undo = compact;
+
+			let event = gb.open_tile(x, y).unwrap();
+			assert_eq!(matches!(event, GameBoardEvent::OpenSeed { .. }), compact);
+
+			gb.undo_move(&event).unwrap();
+			assert_eq!(gb.render(), fresh);
+			assert_eq!(gb.opened(), 0);
+		}
+	}
+}
+
+#[test]
+fn is_solvable_from_here_finds_forced_guesses() {
+	// the 1 2 1 row is deduced with subsets
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+	assert!(gb.is_solvable_from_here());
+	assert_eq!(gb.opened(), 6);
+
+	// the two tiles above the ones are a 50/50
+	let mut gb = board("10\n00\n00");
+	gb.open_tile(0, 2).unwrap();
+	assert!(!gb.is_solvable_from_here());
+
+	gb.open_tile(0, 0).unwrap_err();
+	assert!(!gb.is_solvable_from_here());
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn from_flatboard_round_trips_count_grid() {
+	let gb = GameBoard::new_seeded(12, 7, 20, 8).unwrap();
+	let grid = gb.count_grid();
+
+	let to_tile = |&c: &u8| Tile::try_from(c).unwrap_or(Tile::Bomb);
+	let mut tiles = FlatBoard::new(7, 12, Tile::Zero);
+	for (tile, count) in tiles.iter_backing_mut().zip(grid.iter_backing()) {
+		*tile = to_tile(count);
+	}
+
+	let rebuilt = GameBoard::from_flatboard(&tiles).unwrap();
+	assert_eq!(rebuilt.count_grid(), grid);
+	assert_eq!(rebuilt.bomb_count(), 20);
+
+	// wrong numbers are recomputed from the bombs
+	let mut wrong = FlatBoard::new(1, 3, Tile::Eight);
+	wrong[0][0] = Tile::Bomb;
+	let rebuilt = GameBoard::from_flatboard(&wrong).unwrap();
+	assert_eq!(
+		rebuilt
+			.count_grid()
+			.iter_backing()
+			.copied()
+			.collect::<Vec<_>>(),
+		[GameBoard::COUNT_GRID_BOMB, 1, 0]
+	);
+}
+
+#[test]
+fn difficulty_estimate_counts_guesses_of_the_current_state() {
+	// the 1 2 1 row needs no guess, the 50/50 needs one
+	let mut solvable = board("101\n000\n000");
+	solvable.open_tile(1, 2).unwrap();
+
+	let metrics = solvable.difficulty_estimate();
+	assert_eq!(metrics.guess_points, 0);
+	assert_eq!(metrics.bv3, solvable.board_3bv());
+	assert!((metrics.density - 2.0 / 9.0).abs() < f64::EPSILON);
+
+	let mut coin_flip = board("10\n00\n00");
+	coin_flip.open_tile(0, 2).unwrap();
+	assert_eq!(coin_flip.difficulty_estimate().guess_points, 1);
+
+	// an untouched board has to start with a guess
+	assert!(board("100\n000\n001").difficulty_estimate().guess_points >= 1);
+}
+
+#[test]
+fn board_format_round_trips_and_rejects_bad_input() {
+	let gb = GameBoard::new_seeded(13, 5, 17, 6).unwrap();
+	let text = gb.to_board_format();
+
+	let parsed = GameBoard::from_board_format(&text).unwrap();
+	assert_eq!(parsed.to_board_format(), text);
+	assert_eq!(parsed.dimensions(), (13, 5));
+	assert_eq!(parsed.bomb_count(), 17);
+
+	// surrounding whitespace and blank lines are ignored
+	assert!(GameBoard::from_board_format("\n  010\n\n 000 \n").is_ok());
+
+	assert!(matches!(
+		GameBoard::from_board_format("010\n00\n000"),
+		Err(BoardFormatError::RaggedRow(1))
+	));
+	assert!(matches!(
+		GameBoard::from_board_format("010\n0x0"),
+		Err(BoardFormatError::InvalidChar('x'))
+	));
+	assert!(matches!(
+		GameBoard::from_board_format(""),
+		Err(BoardFormatError::Board(NewBoardError::ZeroDimension))
+	));
+}
+
+#[test]
+fn clearing_is_the_generation_argument() {
+	assert_eq!(
+		GameBoard::with_clearing(9, 9, 10, 3, 7).unwrap().clearing(),
+		Some((3, 7))
+	);
+	assert_eq!(
+		GameBoard::with_clearing_seeded(9, 9, 10, 0, 8, 1)
+			.unwrap()
+			.clearing(),
+		Some((0, 8))
+	);
+	assert_eq!(GameBoard::new(9, 9, 10).unwrap().clearing(), None);
+}
+
+#[test]
+fn rendered_board_displays_as_ascii() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+
+	assert_eq!(gb.render().to_string(), "F1 \n#21\n###\n");
+
+	// the correct flag is kept through the loss
+	gb.open_tile(2, 2).unwrap_err();
+	assert_eq!(gb.render().to_string(), "F1 \n#21\n##@\n");
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn flag_accuracy_splits_correct_and_wrong_flags() {
+	let mut gb = board("101\n000\n010");
+	assert_eq!(gb.flag_accuracy(), (0, 0));
+
+	for (x, y) in [(0, 0), (2, 0), (1, 1), (2, 2)] {
+		gb.flag_tile(x, y).unwrap();
+	}
+
+	assert_eq!(gb.flag_accuracy(), (2, 2));
+}
+
+#[test]
+fn daily_boards_depend_only_on_date_and_difficulty() {
+	let today = GameBoard::daily((2024, 3, 9), Difficulty::Intermediate).unwrap();
+	let again = GameBoard::daily((2024, 3, 9), Difficulty::Intermediate).unwrap();
+	let tomorrow = GameBoard::daily((2024, 3, 10), Difficulty::Intermediate).unwrap();
+
+	assert_eq!(today.to_board_format(), again.to_board_format());
+	assert_ne!(today.to_board_format(), tomorrow.to_board_format());
+	assert_ne!(
+		GameBoard::daily_seed((2024, 3, 9), Difficulty::Intermediate),
+		GameBoard::daily_seed((2024, 3, 9), Difficulty::Expert)
+	);
+	assert_eq!(
+		today.seed(),
+		Some(GameBoard::daily_seed(
+			(2024, 3, 9),
+			Difficulty::Intermediate
+		))
+	);
+
+	// a changed derivation changes every past and future puzzle
+	assert_eq!(
+		GameBoard::daily_seed((2024, 3, 9), Difficulty::Beginner),
+		12_728_529_881_446_816_758
+	);
+}
+
+#[test]
+fn preview_chord_lists_the_tiles_the_chord_opens() {
+	let mut gb = board("100\n000\n001");
+	gb.options_mut().auto_cascade = false;
+	gb.open_tile(1, 1).unwrap();
+	gb.open_tile(1, 0).unwrap();
+
+	assert!(matches!(
+		gb.preview_chord(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+	assert!(matches!(
+		gb.preview_chord(0, 1),
+		Err(UnopenableError::NotANumber)
+	));
+
+	gb.flag_tile(0, 0).unwrap();
+	gb.flag_tile(2, 2).unwrap();
+
+	let mut preview = gb.preview_chord(1, 1).unwrap();
+	let before = gb.render();
+
+	gb.open_around(1, 1).unwrap();
+	let mut opened: Vec<_> = diff(&before, &gb.render())
+		.unwrap()
+		.into_iter()
+		.map(|(x, y, _)| (x, y))
+		.collect();
+
+	preview.sort_unstable();
+	opened.sort_unstable();
+	assert_eq!(preview, opened);
+	assert_eq!(preview.len(), 5);
+}
+
+#[test]
+fn restart_same_seed_replays_the_same_layout() {
+	let mut gb = GameBoard::with_clearing_seeded(9, 9, 10, 4, 4, 21).unwrap();
+	gb.open_tile(4, 4).unwrap();
+	gb.finalize_loss();
+
+	let retry = gb.restart_same_seed();
+
+	assert_eq!(retry.to_board_format(), gb.to_board_format());
+	assert_eq!((retry.seed(), retry.clearing()), (Some(21), Some((4, 4))));
+	assert_eq!(retry.state(), GameState::Playing);
+	assert_eq!(retry.opened() + retry.flagged(), 0);
+	assert!(retry
+		.render()
+		.iter()
+		.flatten()
+		.all(|&t| t == VisibleTile::NotVisible));
+}
+
+#[test]
+fn auto_flag_forced_flags_only_proven_bombs() {
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_flag_forced = true;
+
+	let event = gb.open_tile(2, 2).unwrap();
+	assert_eq!(gb.get_board_tile(0, 0), Some(VisibleTile::Flagged));
+	assert!(matches!(
+		&event,
+		GameBoardEvent::Sequence(events) if events.last() == Some(&GameBoardEvent::flag_tile(0, 0))
+	));
+
+	// the extra flag is part of the event, so undo removes it too
+	gb.undo_move(&event).unwrap();
+	assert_eq!(gb.opened() + gb.flagged(), 0);
+
+	// either tile above the ones could be the bomb
+	let mut gb = board("10\n00\n00");
+	gb.options_mut().auto_flag_forced = true;
+	gb.open_tile(0, 2).unwrap();
+	assert_eq!(gb.flagged(), 0);
+}
+
+#[test]
+fn encoded_layouts_round_trip_at_several_sizes() {
+	for (x, y, bombs, seed) in [
+		(1, 1, 0, 0),
+		(3, 3, 2, 1),
+		(9, 9, 10, 2),
+		(30, 16, 99, 3),
+		(7, 5, 34, 4),
+	] {
+		let gb = GameBoard::new_seeded(x, y, bombs, seed).unwrap();
+		let bytes = gb.encode_layout();
+
+		let area = usize::from(x) * usize::from(y);
+		assert_eq!(bytes.len(), 8 + area.div_ceil(8));
+
+		let decoded = GameBoard::decode_layout(&bytes).unwrap();
+		assert_eq!(decoded.to_board_format(), gb.to_board_format());
+		assert_eq!(decoded.bomb_count(), bombs);
+	}
+}
+
+#[test]
+fn bad_layout_buffers_are_rejected() {
+	let bytes = board("100\n000\n001").encode_layout();
+	assert_eq!(bytes.len(), 10);
+
+	assert!(matches!(
+		GameBoard::decode_layout(&bytes[..5]),
+		Err(DecodeError::Truncated)
+	));
+	assert!(matches!(
+		GameBoard::decode_layout(&bytes[..9]),
+		Err(DecodeError::Truncated)
+	));
+
+	let mut long = bytes.clone();
+	long.push(0);
+	assert!(matches!(
+		GameBoard::decode_layout(&long),
+		Err(DecodeError::Oversized)
+	));
+
+	// the 9 tiles leave 7 padding bits in the last byte
+	let mut padded = bytes.clone();
+	padded[9] |= 0b10;
+	assert!(matches!(
+		GameBoard::decode_layout(&padded),
+		Err(DecodeError::NonZeroPadding)
+	));
+
+	let mut miscounted = bytes.clone();
+	miscounted[4] = 3;
+	assert!(matches!(
+		GameBoard::decode_layout(&miscounted),
+		Err(DecodeError::BombCountMismatch)
+	));
+
+	let mut empty = bytes;
+	empty[0] = 0;
+	assert!(matches!(
+		GameBoard::decode_layout(&empty),
+		Err(DecodeError::Board(_))
+	));
+}
+
+#[test]
+fn right_clicks_on_opened_tiles_chord_or_do_nothing() {
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_cascade = false;
+	gb.open_tile(1, 1).unwrap();
+	gb.open_tile(2, 2).unwrap();
+
+	// the low level flag stays strict
+	assert!(matches!(
+		gb.flag_tile(1, 1),
+		Err(UnopenableError::AlreadyOpen)
+	));
+
+	// a zero and a number missing its flag change nothing
+	let before = gb.render();
+	gb.do_event(KeyEvent::Mouse2(2, 2)).unwrap();
+	gb.do_event(KeyEvent::Mouse2(1, 1)).unwrap();
+	assert_eq!(gb.render(), before);
+
+	gb.do_event(KeyEvent::Mouse2(0, 0)).unwrap();
+	assert_eq!(gb.flagged(), 1);
+
+	// once flagged the number chords its neighbors, winning the game
+	gb.do_event(KeyEvent::Mouse2(1, 1)).unwrap();
+	assert_eq!(gb.opened(), 8);
+	assert_eq!(gb.state(), GameState::Won);
+}
+
+#[test]
+fn memory_budgets_allow_boards_up_to_the_budget() {
+	let tile = std::mem::size_of::<BoardTile>();
+	let beginner = 9 * 9 * tile;
+
+	assert!(GameBoard::validate_board_within(9, 9, 10, beginner).is_ok());
+	assert!(matches!(
+		GameBoard::validate_board_within(9, 9, 10, beginner - 1),
+		Err(NewBoardError::MemoryBudgetExceeded)
+	));
+
+	// the regular checks come first
+	assert!(matches!(
+		GameBoard::validate_board_within(9, 9, 82, 0),
+		Err(NewBoardError::BombOverflow)
+	));
+
+	// a 10k by 10k board is refused before anything is allocated
+	assert!(matches!(
+		GameBoard::validate_board_within(10_000, 10_000, 10, 64 << 20),
+		Err(NewBoardError::MemoryBudgetExceeded)
+	));
+}
+
+#[test]
+fn open_region_opens_all_tiles_or_none() {
+	let mut gb = board("100\n000\n000");
+	let before = gb.render();
+
+	// a bomb anywhere in the set opens nothing and does not lose the game
+	assert!(matches!(
+		gb.open_region(&[(2, 2), (1, 1), (0, 0)]),
+		Err(UnopenableError::RegionContainsBomb(0, 0))
+	));
+	assert_eq!(gb.state(), GameState::Playing);
+	assert!(matches!(
+		gb.open_region(&[(2, 2), (3, 0)]),
+		Err(UnopenableError::OutOfBounds)
+	));
+	gb.flag_tile(0, 0).unwrap();
+	assert!(matches!(
+		gb.open_region(&[(2, 2), (0, 0)]),
+		Err(UnopenableError::FlaggedTile)
+	));
+	gb.flag_tile(0, 0).unwrap();
+
+	assert_eq!(gb.render(), before);
+	assert_eq!(gb.state(), GameState::Playing);
+
+	// the zero at 2/2 does not cascade, and repeats are opened once
+	let event = gb.open_region(&[(2, 2), (1, 1), (2, 2)]).unwrap();
+	assert_eq!(event, GameBoardEvent::from(vec![(2, 2), (1, 1)]));
+	assert_eq!(gb.opened(), 2);
+}
+
+#[test]
+fn render_html_has_a_cell_per_tile() {
+	let mut gb = board("1000\n0000\n0000");
+	gb.flag_tile(0, 0).unwrap();
+	gb.open_tile(3, 2).unwrap();
+
+	let html = gb.render_html();
+
+	assert!(html.starts_with("<table class=\"board\">"));
+	assert_eq!(html.matches("<tr>").count(), 3);
+	assert_eq!(html.matches("<td ").count(), 12);
+	assert_eq!(html.matches("tile-flag").count(), 1);
+	assert_eq!(html.matches("<td class=\"tile tile-1\">1</td>").count(), 3);
+	assert_eq!(html.matches("tile-0").count(), 8);
+	assert!(!html.contains("style="));
+}
+
+#[test]
+fn unopened_around_counts_only_in_bounds_hidden_tiles() {
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_cascade = false;
+
+	// corners have 3 neighbors, edges 5 and the center 8
+	assert_eq!(gb.unopened_around(0, 0), 3);
+	assert_eq!(gb.unopened_around(1, 0), 5);
+	assert_eq!(gb.unopened_around(2, 1), 5);
+	assert_eq!(gb.unopened_around(1, 1), 8);
+	assert_eq!(gb.unopened_around(3, 0), 0);
+	assert_eq!(gb.unopened_around(0, 3), 0);
+
+	// opened and flagged neighbors are not counted
+	gb.flag_tile(0, 0).unwrap();
+	gb.open_tile(2, 2).unwrap();
+	assert_eq!(gb.unopened_around(1, 0), 4);
+	assert_eq!(gb.unopened_around(1, 1), 6);
+	assert_eq!(gb.unopened_around(2, 2), 3);
+}
+
+#[test]
+fn annotations_do_not_count_as_flags() {
+	let mut gb = board("100\n000\n000");
+	gb.options_mut().auto_cascade = false;
+	gb.open_tile(1, 1).unwrap();
+
+	gb.annotate(0, 0, Some('?')).unwrap();
+	assert!(matches!(
+		gb.annotate(3, 0, Some('?')),
+		Err(UnopenableError::OutOfBounds)
+	));
+
+	assert_eq!(gb.annotation(0, 0), Some('?'));
+	assert_eq!(gb.flagged(), 0);
+	assert_eq!(gb.render()[0][0], VisibleTile::NotVisible);
+
+	// a mark is not a flag for chording
+	assert!(matches!(
+		gb.open_around(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+	assert_eq!(gb.opened(), 1);
+
+	gb.flag_tile(0, 0).unwrap();
+	gb.open_around(1, 1).unwrap();
+	assert_eq!(gb.opened(), 8);
+	assert_eq!(gb.annotation(0, 0), Some('?'));
+
+	gb.clear_annotations();
+	assert_eq!(gb.annotation(0, 0), None);
+}
+
+#[test]
+fn openings_are_split_by_a_wall_of_bombs() {
+	let gb = board("00100\n00100\n00100");
+
+	let sorted = |mut tiles: Vec<(u16, u16)>| {
+		tiles.sort_unstable();
+		tiles
+	};
+	let column = |x| sorted((0..3).flat_map(|y| [(x, y), (x + 1, y)]).collect());
+
+	let openings: Vec<_> = gb.openings().into_iter().map(sorted).collect();
+
+	assert_eq!(openings, [column(0), column(3)]);
+	assert_eq!(gb.board_3bv(), 2);
+
+	// a bomb surrounded by numbers has no opening
+	assert!(board("000\n010\n000").openings().is_empty());
+}
+
+#[test]
+fn cells_match_a_loop_over_render() {
+	let mut gb = board("100\n000\n001");
+	gb.open_tile(2, 0).unwrap();
+	gb.flag_tile(0, 0).unwrap();
+
+	let looped = |gb: &GameBoard| {
+		let render = gb.render();
+		let mut cells = vec![];
+
+		for y in gb.y_range() {
+			for x in gb.x_range() {
+				cells.push(((x, y), render[usize::from(y)][usize::from(x)]));
+			}
+		}
+
+		cells
+	};
+
+	assert_eq!(gb.cells().collect::<Vec<_>>(), looped(&gb));
+
+	// the detonated bomb and wrong flags are shown after a loss
+	gb.flag_tile(0, 0).unwrap();
+	gb.flag_tile(1, 2).unwrap();
+	assert!(gb.open_tile(2, 2).is_err());
+
+	let cells: Vec<_> = gb.cells().collect();
+	assert_eq!(cells, looped(&gb));
+	assert_eq!(cells[8], ((2, 2), VisibleTile::DetonatedBomb));
+	assert_eq!(cells[7], ((1, 2), VisibleTile::WrongFlag));
+}
+
+#[test]
+fn chord_policies_on_a_mismatched_number() {
+	let position = |policy, flags: &[(u16, u16)]| {
+		let mut gb = board("100\n000\n001");
+		gb.options_mut().auto_cascade = false;
+		gb.options_mut().chord_policy = policy;
+		gb.open_tile(1, 1).unwrap();
+
+		for &(x, y) in flags {
+			gb.flag_tile(x, y).unwrap();
+		}
+
+		gb
+	};
+
+	// the 2 at 1/1 with a single flag
+	let mut strict = position(ChordPolicy::Strict, &[(0, 0)]);
+	assert!(matches!(
+		strict.preview_chord(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+	assert!(matches!(
+		strict.open_around(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+
+	let mut lenient = position(ChordPolicy::Lenient, &[(0, 0)]);
+	let before = lenient.render();
+	assert!(lenient.preview_chord(1, 1).unwrap().is_empty());
+	assert_eq!(
+		lenient.open_around(1, 1).unwrap(),
+		GameBoardEvent::from(vec![])
+	);
+	assert_eq!(lenient.render(), before);
+
+	// an extra flag beside both bombs still opens the rest
+	let mut strict = position(ChordPolicy::Strict, &[(0, 0), (2, 2), (1, 0)]);
+	assert!(matches!(
+		strict.open_around(1, 1),
+		Err(UnopenableError::FlagCountMismatch)
+	));
+
+	let mut lenient = position(ChordPolicy::Lenient, &[(0, 0), (2, 2), (1, 0)]);
+	let preview = lenient.preview_chord(1, 1).unwrap();
+	assert_eq!(preview.len(), 5);
+	lenient.open_around(1, 1).unwrap();
+	assert_eq!(lenient.opened(), 6);
+
+	// an extra flag beside an unflagged bomb hits it
+	let mut lenient = position(ChordPolicy::Lenient, &[(0, 0), (1, 0), (2, 0)]);
+	assert!(matches!(
+		lenient.open_around(1, 1),
+		Err(UnopenableError::BombHit(2, 2))
+	));
+	assert_eq!(lenient.state(), GameState::Lost);
+}
+
+#[test]
+fn opening_size_at_matches_the_opened_count() {
+	let layout = "10000\n00000\n00010\n00000";
+
+	for y in 0..4 {
+		for x in 0..5 {
+			let mut gb = board(layout);
+			let size = gb.opening_size_at(x, y);
+
+			match gb.open_tile(x, y) {
+				Ok(_) => assert_eq!(size, gb.opened(), "opening {x}/{y}"),
+				Err(_) => assert_eq!(size, 0, "bomb {x}/{y}"),
+			}
+		}
+	}
+
+	// a number opens alone even next to a zero, and open or flagged tiles open nothing
+	let mut gb = board("110\n000\n000");
+	assert_eq!(gb.opening_size_at(0, 1), 1);
+	gb.open_tile(2, 2).unwrap();
+	assert_eq!(gb.opening_size_at(2, 2), 0);
+	gb.flag_tile(0, 0).unwrap();
+	assert_eq!(gb.opening_size_at(0, 0), 0);
+}
+
+#[test]
+fn in_bounds_at_the_edges_of_the_board() {
+	let gb = board("0000\n0000\n0000");
+
+	assert!(gb.in_bounds(0, 0));
+	assert!(gb.in_bounds(3, 2));
+	assert!(!gb.in_bounds(4, 0));
+	assert!(!gb.in_bounds(0, 3));
+	assert!(!gb.in_bounds(4, 3));
+	assert!(!gb.in_bounds(u16::MAX, u16::MAX));
+
+	// every in bounds tile has a tile, and no other does
+	for y in 0..=3 {
+		for x in 0..=4 {
+			assert_eq!(gb.in_bounds(x, y), gb.get_board_tile(x, y).is_some());
+		}
+	}
+}
+
+#[test]
+fn try_finish_opens_the_rest_only_when_deducible() {
+	// the 1 2 1 row proves both bombs, leaving the tile between them safe
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+
+	assert_eq!(gb.try_finish().unwrap(), GameBoardEvent::from(vec![(1, 0)]));
+	assert_eq!(gb.state(), GameState::Won);
+	assert_eq!(gb.flagged(), 0);
+	assert!(matches!(gb.try_finish(), Err(UnopenableError::GameOver)));
+
+	// a 50/50 is never guessed
+	let mut gb = board("10\n00\n00");
+	gb.open_tile(0, 2).unwrap();
+	let before = gb.render();
+	assert!(matches!(
+		gb.try_finish(),
+		Err(UnopenableError::NotDeducible)
+	));
+	assert_eq!(gb.render(), before);
+
+	// a wrong flag is trusted and contradicts the numbers
+	let mut gb = board("101\n000\n000");
+	gb.open_tile(1, 2).unwrap();
+	gb.flag_tile(1, 0).unwrap();
+	assert!(matches!(
+		gb.try_finish(),
+		Err(UnopenableError::NotDeducible)
+	));
+	assert_eq!(gb.state(), GameState::Playing);
+}
+
+#[test]
+fn bounding_box_of_scattered_tiles_and_events() {
+	assert_eq!(bounding_box(&[]), None);
+	assert_eq!(bounding_box(&[(4, 2)]), Some(((4, 2), (4, 2))));
+	assert_eq!(
+		bounding_box(&[(5, 1), (0, 7), (3, 3), (9, 0), (2, 8)]),
+		Some(((0, 0), (9, 8)))
+	);
+
+	assert_eq!(
+		GameBoardEvent::flag_tile(3, 1).bounding_box(),
+		Some(((3, 1), (3, 1)))
+	);
+	assert_eq!(GameBoardEvent::from(vec![]).bounding_box(), None);
+
+	// a cascade from the corner spreads around the bomb to every other corner
+	let mut gb = board("0000\n0000\n0001\n0000");
+	let event = gb.open_tile(0, 0).unwrap();
+	assert_eq!(event.bounding_box(), Some(((0, 0), (3, 3))));
+
+	let mut gb = board("0000\n0000\n0001\n0000");
+	gb.options_mut().auto_cascade = false;
+	let first = gb.open_tile(1, 0).unwrap();
+	let second = gb.open_tile(0, 3).unwrap();
+	assert_eq!(
+		GameBoardEvent::Sequence(vec![first, second].into()).bounding_box(),
+		Some(((0, 0), (1, 3)))
+	);
+}
+
+#[test]
+fn single_safe_boards_reach_the_density_ceiling() {
+	// every tile but the clicked one is a bomb
+	for (cx, cy) in [(0, 0), (4, 4), (8, 3), (2, 8)] {
+		let mut gb = GameBoard::with_single_safe(9, 9, 80, cx, cy).unwrap();
+
+		assert_eq!(gb.clearing(), Some((cx, cy)));
+		gb.open_tile(cx, cy).unwrap();
+		assert_eq!(gb.state(), GameState::Won);
+	}
+
+	for _ in 0..20 {
+		let mut gb = GameBoard::with_single_safe(4, 4, 14, 1, 2).unwrap();
+		assert!(gb.open_tile(1, 2).is_ok());
+	}
+
+	assert!(matches!(
+		GameBoard::with_single_safe(9, 9, 81, 4, 4),
+		Err(NewBoardError::BombOverflow)
+	));
+	assert!(matches!(
+		GameBoard::with_single_safe(9, 9, 10, 9, 4),
+		Err(NewBoardError::SizeConstraintOverflow)
+	));
+	// the 3x3 clearing does not fit the same density
+	assert!(GameBoard::with_clearing(4, 4, 14, 1, 2).is_err());
+}
+
+#[test]
+fn restore_reverts_every_move_since_the_snapshot() {
+	let mut gb = board("100\n000\n001");
+	gb.options_mut().auto_cascade = false;
+	gb.open_tile(1, 1).unwrap();
+
+	let snapshot = gb.snapshot();
+	let (render, opened) = (gb.render(), gb.opened());
+
+	gb.flag_tile(0, 0).unwrap();
+	gb.open_tile(2, 0).unwrap();
+	gb.annotate(1, 2, Some('?')).unwrap();
+	assert!(gb.open_tile(2, 2).is_err());
+	assert_eq!(gb.state(), GameState::Lost);
+
+	gb.restore(&snapshot).unwrap();
+
+	assert_eq!(gb.render(), render);
+	assert_eq!((gb.opened(), gb.flagged()), (opened, 0));
+	assert_eq!(gb.state(), GameState::Playing);
+	assert_eq!(gb.annotation(1, 2), None);
+
+	// the snapshot can be restored again after more moves
+	gb.open_tile(0, 2).unwrap();
+	gb.restore(&snapshot).unwrap();
+	assert_eq!(gb.render(), render);
+
+	let unsupported = BoardSnapshot::new(());
+	assert!(matches!(
+		gb.restore(&unsupported),
+		Err(RestoreError::WrongBoard)
+	));
+}
+
+#[test]
+fn mine_counts_at_the_boundaries() {
+	assert_eq!(MineCount::new(0), Some(MineCount::ZERO));
+	assert_eq!(MineCount::new(8), Some(MineCount::MAX));
+	assert_eq!(MineCount::new(9), None);
+
+	assert_eq!(Tile::from(MineCount::ZERO), Tile::Zero);
+	assert_eq!(Tile::from(MineCount::MAX), Tile::Eight);
+	assert_eq!(MineCount::try_from(Tile::Eight), Ok(MineCount::MAX));
+	assert_eq!(MineCount::try_from(Tile::Bomb), Err(()));
+
+	assert_eq!(MineCount::MAX.checked_add(1), None);
+	assert_eq!(MineCount::MAX.saturating_add(1), MineCount::MAX);
+	assert_eq!(MineCount::ZERO.saturating_sub(1), MineCount::ZERO);
+	assert_eq!(MineCount::ZERO.checked_add(8), Some(MineCount::MAX));
+
+	// a tile surrounded by bombs counts 8, down to 0 once every one is flagged
+	let mut gb = board("1110\n1010\n1110");
+	gb.open_tile(1, 1).unwrap();
+	assert_eq!(gb.remaining_mines_around(1, 1), Some(MineCount::MAX));
+
+	for y in 0..3 {
+		for x in 0..3 {
+			if (x, y) != (1, 1) {
+				gb.flag_tile(x, y).unwrap();
+			}
+		}
+	}
+	assert_eq!(gb.remaining_mines_around(1, 1), Some(MineCount::ZERO));
+}
+
+#[cfg(feature = "assist")]
+#[test]
+fn neighbor_bomb_counts_at_the_boundaries() {
+	let gb = board("111\n101\n111");
+
+	assert_eq!(gb.neighbor_bomb_count(1, 1), Some(MineCount::MAX));
+	assert_eq!(gb.neighbor_bomb_count(0, 0), None);
+
+	let gb = board("000\n000\n000");
+	assert_eq!(gb.neighbor_bomb_count(1, 1), Some(MineCount::ZERO));
+}
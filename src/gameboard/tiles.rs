@@ -1,5 +1,7 @@
 use std::fmt;
 
+use super::FlatBoard;
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum Tile {
 	Zero = 0,
@@ -16,39 +18,62 @@ pub enum Tile {
 
 impl Tile {
 	/// returns true if tile is of variant Bomb
+	#[must_use]
 	pub const fn is_bomb(self) -> bool {
-		matches!(self, Tile::Bomb)
+		matches!(self, Self::Bomb)
 	}
 
 	/// Returns self as count of bombs in 8 surrounding squares, or None if is a bomb
+	#[must_use]
 	pub const fn as_count(self) -> Option<u8> {
 		match self {
-			Tile::Bomb => None,
+			Self::Bomb => None,
 			v => Some(v as u8),
 		}
 	}
 
+	/// returns self as a [`MineCount`], or None if is a bomb
+	#[must_use]
+	pub const fn mine_count(self) -> Option<MineCount> {
+		match self.as_count() {
+			Some(v) => Some(MineCount(v)),
+			None => None,
+		}
+	}
+
+	/// returns count as a single width char, where a bomb is represented by the bomb glyph of glyphs
+	#[must_use]
+	pub const fn as_glyph(self, glyphs: &Glyphs) -> char {
+		match self {
+			Self::Zero => ' ',
+			Self::Bomb => glyphs.bomb,
+			// all other tiles are in the range 1..=8, so this is always a digit
+			v => (b'0' + v as u8) as char,
+		}
+	}
+
 	/// returns count as a single width string, where a bomb is represented by a B
 	/// used as a pre end user stage for naked processing
+	#[must_use]
 	pub const fn as_str_count(self) -> &'static str {
 		match self {
-			Tile::Zero => " ",
-			Tile::One => "1",
-			Tile::Two => "2",
-			Tile::Three => "3",
-			Tile::Four => "4",
-			Tile::Five => "5",
-			Tile::Six => "6",
-			Tile::Seven => "7",
-			Tile::Eight => "8",
-			Tile::Bomb => "B",
+			Self::Zero => " ",
+			Self::One => "1",
+			Self::Two => "2",
+			Self::Three => "3",
+			Self::Four => "4",
+			Self::Five => "5",
+			Self::Six => "6",
+			Self::Seven => "7",
+			Self::Eight => "8",
+			Self::Bomb => "B",
 		}
 	}
 }
 
 impl fmt::Display for Tile {
 	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-		use Tile::*;
+		use Tile::{Bomb, Eight, Five, Four, One, Seven, Six, Three, Two, Zero};
 
 		const C: &str = "\u{1b}[";
 
@@ -62,16 +87,152 @@ impl fmt::Display for Tile {
 			Six => write!(f, "{C}36m6 "),
 			Seven => write!(f, "{C}30m7 "),
 			Eight => write!(f, "{C}90m8 "),
-			Bomb => write!(f, "{C}31mB "),
+			Bomb => write!(f, "{C}31m{} ", Glyphs::DEFAULT.bomb),
+		}
+	}
+}
+
+/// the symbols used to render tiles that are not a number
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Glyphs {
+	/// a flagged tile
+	pub flag: char,
+	/// a bomb revealed at the end of a game
+	pub bomb: char,
+	/// the bomb that ended the game
+	pub mine_hit: char,
+	/// a flag that was not on a bomb, shown once the game is lost
+	pub wrong_flag: char,
+	/// a tile marked as uncertain
+	pub question: char,
+}
+
+impl Glyphs {
+	/// the default glyphs, using unicode for the flag
+	pub const DEFAULT: Self = Self {
+		flag: '\u{2691}',
+		bomb: 'B',
+		mine_hit: 'B',
+		wrong_flag: 'X',
+		question: '?',
+	};
+
+	/// glyphs for terminals with poor unicode support
+	pub const ASCII: Self = Self {
+		flag: 'F',
+		bomb: '*',
+		mine_hit: '@',
+		wrong_flag: 'X',
+		question: '?',
+	};
+}
+
+impl Default for Glyphs {
+	fn default() -> Self {
+		Self::DEFAULT
+	}
+}
+
+/// a count of bombs around a tile, which is always in the range 0..=8 so it converts to a [`Tile`] without failing
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct MineCount(u8);
+
+impl MineCount {
+	pub const ZERO: Self = Self(0);
+	/// the most bombs a tile can have around it
+	pub const MAX: Self = Self(8);
+
+	/// returns v as a count, or None if it is more than 8
+	#[must_use]
+	pub const fn new(v: u8) -> Option<Self> {
+		if v <= Self::MAX.0 {
+			Some(Self(v))
+		} else {
+			None
 		}
 	}
+
+	#[must_use]
+	pub const fn get(self) -> u8 {
+		self.0
+	}
+
+	/// returns self plus v, or None if it is more than 8
+	#[must_use]
+	pub const fn checked_add(self, v: u8) -> Option<Self> {
+		match self.0.checked_add(v) {
+			Some(sum) => Self::new(sum),
+			None => None,
+		}
+	}
+
+	/// returns self plus v, stopping at [`MineCount::MAX`]
+	#[must_use]
+	pub const fn saturating_add(self, v: u8) -> Self {
+		match self.checked_add(v) {
+			Some(sum) => sum,
+			None => Self::MAX,
+		}
+	}
+
+	/// returns self minus v, stopping at [`MineCount::ZERO`]
+	#[must_use]
+	pub const fn saturating_sub(self, v: u8) -> Self {
+		Self(self.0.saturating_sub(v))
+	}
+
+	/// returns the numbered tile showing this count
+	#[must_use]
+	pub const fn as_tile(self) -> Tile {
+		use Tile::{Eight, Five, Four, One, Seven, Six, Three, Two, Zero};
+
+		match self.0 {
+			0 => Zero,
+			1 => One,
+			2 => Two,
+			3 => Three,
+			4 => Four,
+			5 => Five,
+			6 => Six,
+			7 => Seven,
+			// a count is never built above 8
+			_ => Eight,
+		}
+	}
+}
+
+impl From<MineCount> for Tile {
+	fn from(count: MineCount) -> Self {
+		count.as_tile()
+	}
+}
+
+impl From<MineCount> for u8 {
+	fn from(count: MineCount) -> Self {
+		count.0
+	}
+}
+
+/// fails for [`Tile::Bomb`], which is not a count
+impl TryFrom<Tile> for MineCount {
+	type Error = ();
+
+	fn try_from(tile: Tile) -> Result<Self, Self::Error> {
+		tile.mine_count().ok_or(())
+	}
+}
+
+impl fmt::Display for MineCount {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
 }
 
 impl TryFrom<u8> for Tile {
 	type Error = ();
 
 	fn try_from(v: u8) -> Result<Self, Self::Error> {
-		use Tile::*;
+		use Tile::{Eight, Five, Four, One, Seven, Six, Three, Two, Zero};
 
 		Ok(match v {
 			0 => Zero,
@@ -95,6 +256,74 @@ pub(super) enum Visibility {
 	Flagged,
 }
 
+/// the visibility of every tile of a board packed as 2 bits per tile in row major order
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct VisibilityMask {
+	dimensions: (u16, u16),
+	data: Box<[u8]>,
+}
+
+impl VisibilityMask {
+	const TILES_PER_BYTE: usize = 4;
+
+	/// packs visibilities in row major order for a board of x/y dimensions
+	pub(super) fn pack(dimensions: (u16, u16), tiles: impl Iterator<Item = Visibility>) -> Self {
+		let mut data = vec![0u8; Self::byte_len(dimensions)];
+
+		for (i, v) in tiles.enumerate() {
+			let bits = match v {
+				Visibility::NotVisible => 0u8,
+				Visibility::Visible => 1,
+				Visibility::Flagged => 2,
+			};
+
+			data[i / Self::TILES_PER_BYTE] |= bits << ((i % Self::TILES_PER_BYTE) * 2);
+		}
+
+		Self {
+			dimensions,
+			data: data.into(),
+		}
+	}
+
+	/// returns the visibility of the tile at a row major index, or [`None`] if it is out of bounds or not a valid visibility
+	pub(super) fn get(&self, i: usize) -> Option<Visibility> {
+		let byte = self.data.get(i / Self::TILES_PER_BYTE)?;
+
+		match (byte >> ((i % Self::TILES_PER_BYTE) * 2)) & 0b11 {
+			0 => Some(Visibility::NotVisible),
+			1 => Some(Visibility::Visible),
+			2 => Some(Visibility::Flagged),
+			_ => None,
+		}
+	}
+
+	/// returns the byte length of a mask of a board of x/y dimensions
+	pub(super) fn byte_len(dimensions: (u16, u16)) -> usize {
+		(usize::from(dimensions.0) * usize::from(dimensions.1)).div_ceil(Self::TILES_PER_BYTE)
+	}
+
+	/// rebuilds a mask from bytes returned by [`VisibilityMask::as_bytes`], or [`None`] if it is the wrong length for the dimensions
+	pub(super) fn from_bytes(dimensions: (u16, u16), bytes: &[u8]) -> Option<Self> {
+		(bytes.len() == Self::byte_len(dimensions)).then(|| Self {
+			dimensions,
+			data: bytes.into(),
+		})
+	}
+
+	/// returns the x/y dimensions of the board this mask was taken from
+	#[must_use]
+	pub const fn dimensions(&self) -> (u16, u16) {
+		self.dimensions
+	}
+
+	/// returns the packed representation
+	#[must_use]
+	pub const fn as_bytes(&self) -> &[u8] {
+		&self.data
+	}
+}
+
 #[derive(Copy, Clone, Debug)]
 pub(super) struct BoardTile {
 	pub(super) tile: Tile,
@@ -119,4 +348,44 @@ pub enum VisibleTile {
 	NotVisible,
 	Visible(Tile),
 	Flagged,
+	/// the bomb that was hit to lose the game
+	DetonatedBomb,
+	/// a flag on a tile that is not a bomb, only shown once the game is lost
+	WrongFlag,
+}
+
+impl VisibleTile {
+	/// returns what the player sees of the tile as a single char, where a covered tile is `#`
+	#[must_use]
+	pub const fn as_glyph(self, glyphs: &Glyphs) -> char {
+		match self {
+			Self::Visible(tile) => tile.as_glyph(glyphs),
+			Self::NotVisible => '#',
+			Self::Flagged => glyphs.flag,
+			Self::DetonatedBomb => glyphs.mine_hit,
+			Self::WrongFlag => glyphs.wrong_flag,
+		}
+	}
+}
+
+/// formats as a single ascii char, see [`VisibleTile::as_glyph`]
+impl fmt::Display for VisibleTile {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "{}", self.as_glyph(&Glyphs::ASCII))
+	}
+}
+
+/// formats a rendered board as an ascii grid with one char per tile and one line per row
+impl fmt::Display for FlatBoard<VisibleTile> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		for row in self.iter() {
+			for tile in row {
+				write!(f, "{tile}")?;
+			}
+
+			writeln!(f)?;
+		}
+
+		Ok(())
+	}
 }
@@ -0,0 +1,110 @@
+//! a headless text mode that plays a game over stdin and stdout
+//!
+//! This module exports [`run`], a play loop over any [`BaseGameBoard`] that reads commands with the [`input`] macro and prints the board with [`BaseGameBoard::render_plain`].
+
+use crate::gameboard::{BaseGameBoard, GameState, UnopenableError};
+use crate::input::input;
+
+/// a command read from a line of input
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Command {
+	Open(u16, u16),
+	Flag(u16, u16),
+	Chord(u16, u16),
+	Quit,
+}
+
+const HELP: &str = "commands: open x y, flag x y, chord x y, quit";
+
+impl Command {
+	/// parses a command, returning [`None`] for anything that is not a known command with the right arguments
+	fn parse(line: &str) -> Option<Self> {
+		let mut words = line.split_whitespace();
+
+		let name = words.next()?;
+
+		if name == "quit" {
+			return words.next().is_none().then_some(Self::Quit);
+		}
+
+		let x = words.next()?.parse().ok()?;
+		let y = words.next()?.parse().ok()?;
+
+		if words.next().is_some() {
+			return None;
+		}
+
+		match name {
+			"open" => Some(Self::Open(x, y)),
+			"flag" => Some(Self::Flag(x, y)),
+			"chord" => Some(Self::Chord(x, y)),
+			_ => None,
+		}
+	}
+}
+
+/// plays a game on board until it is won or lost, or input ends or the player quits
+///
+/// lines that fail to parse print the available commands and prompt again
+pub fn run<B: BaseGameBoard>(board: &mut B) {
+	println!("{HELP}");
+
+	while board.state() == GameState::Playing {
+		print!("{}", board.render_plain());
+
+		let Some(line) = input!("> ") else {
+			return;
+		};
+
+		let Some(command) = Command::parse(&line) else {
+			println!("{HELP}");
+			continue;
+		};
+
+		let res = match command {
+			Command::Open(x, y) => board.open_tile(x, y),
+			Command::Flag(x, y) => board.flag_tile(x, y),
+			Command::Chord(x, y) => board.open_around(x, y),
+			Command::Quit => return,
+		};
+
+		match res {
+			Ok(_) => (),
+			Err(UnopenableError::BombHit(..)) => board.lose_game(),
+			Err(e) => println!("{e}"),
+		}
+	}
+
+	print!("{}", board.render_plain());
+
+	match board.state() {
+		GameState::Won => println!("you won"),
+		GameState::Lost => println!("you lost"),
+		GameState::Playing => (),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn parse_reads_known_commands_only() {
+		assert_eq!(Command::parse("open 3 4"), Some(Command::Open(3, 4)));
+		assert_eq!(Command::parse("  flag 0 1 "), Some(Command::Flag(0, 1)));
+		assert_eq!(Command::parse("chord 2 2"), Some(Command::Chord(2, 2)));
+		assert_eq!(Command::parse("quit"), Some(Command::Quit));
+
+		for line in [
+			"",
+			"open",
+			"open 1",
+			"open 1 2 3",
+			"open -1 2",
+			"dig 1 2",
+			"quit now",
+		] {
+			assert_eq!(Command::parse(line), None, "{line:?}");
+		}
+	}
+}
@@ -0,0 +1,22 @@
+//! line based terminal input helpers
+
+/// prints a prompt, then reads a line of stdin without its line ending, evaluating to [`None`] on end of input or a read error
+///
+/// takes the same arguments as [`print!`]
+macro_rules! input {
+	($($arg:tt)*) => {{
+		use std::io::Write;
+
+		print!($($arg)*);
+		let _ = std::io::stdout().flush();
+
+		let mut line = String::new();
+
+		match std::io::stdin().read_line(&mut line) {
+			Ok(0) | Err(_) => None,
+			Ok(_) => Some(line.trim_end_matches(['\r', '\n']).to_owned()),
+		}
+	}};
+}
+
+pub(crate) use input;
@@ -1,11 +1,12 @@
 //! a lazy initialized game board
 //!
-//! This module exports the [LazyGameBoard], a wrapper around a [BaseGameBoard] that does not init the board until a move has been played.
-//! This can be useful for wrapping [BaseGameBoard]'s that can only be created at the time of a move being made.
+//! This module exports the [`LazyGameBoard`], a wrapper around a [`BaseGameBoard`] that does not init the board until a move has been played.
+//! This can be useful for wrapping [`BaseGameBoard`]'s that can only be created at the time of a move being made.
 
 use super::gameboard::{
-	BaseGameBoard, BaseGameBoard_do_event, FlatBoard, GameBoard, GameBoardEvent, KeyEvent,
-	NewBoardError, UndoError, UnopenableError, VisibleTile,
+	BaseGameBoard, BaseGameBoard_do_event, BoardSnapshot, ChordPolicy, FlatBoard, GameBoard,
+	GameBoardEvent, GameState, KeyEvent, NewBoardError, RestoreError, UndoError, UnopenableError,
+	VisibleTile,
 };
 
 pub struct LazyGameBoard<T: BaseGameBoard>(LazyGameBoardInner<T>);
@@ -16,9 +17,15 @@ enum LazyGameBoardInner<T: BaseGameBoard> {
 	Uninit { x: u16, y: u16, bombs: u32 },
 }
 
+/// the state of a [`LazyGameBoard`] captured by [`BaseGameBoard::snapshot`]
+enum LazySnapshot {
+	Init(BoardSnapshot),
+	Uninit { x: u16, y: u16, bombs: u32 },
+}
+
 impl<T: BaseGameBoard> LazyGameBoard<T> {
 	pub fn new_uninit(x: u16, y: u16, bombs: u32) -> Result<Self, NewBoardError> {
-		GameBoard::validate_board(x, y, bombs, false, None)?;
+		GameBoard::validate_board(x, y, bombs, None)?;
 
 		Ok(Self(Uninit { x, y, bombs }))
 	}
@@ -28,13 +35,19 @@ impl<T: BaseGameBoard> LazyGameBoard<T> {
 macro_rules! lazy_call {
 	($se:ident, $fn_name:ident, $px:ident, $py:ident, $T:ty) => {
 		match $se.0 {
-			Init(ref mut board) => board.$fn_name($px, $py),
+			Init(ref mut board) => {
+				assert_playing(board)?;
+				board.$fn_name($px, $py)
+			}
 			Uninit { x, y, bombs } => {
-				// hack to assert bounds before board creation crashes
-				$se.get_board_tile($px, $py)
-					.ok_or(UnopenableError::OutOfBounds)?;
+				// assert bounds before board creation crashes
+				if !$se.in_bounds($px, $py) {
+					return Err(UnopenableError::OutOfBounds);
+				}
 
-				let mut b = <$T>::with_clearing(x, y, bombs, $px, $py).unwrap();
+				// the board stays uninit, so a move with more room around it can still generate one
+				let mut b = <$T>::with_clearing(x, y, bombs, $px, $py)
+					.map_err(UnopenableError::GenerationFailed)?;
 
 				let res = b.$fn_name($px, $py);
 
@@ -48,6 +61,14 @@ macro_rules! lazy_call {
 
 use LazyGameBoardInner::{Init, Uninit};
 
+/// returns [`UnopenableError::GameOver`] if the game on an init board was already won or lost
+fn assert_playing<B: BaseGameBoard>(board: &B) -> Result<(), UnopenableError> {
+	match board.state() {
+		GameState::Playing => Ok(()),
+		GameState::Won | GameState::Lost => Err(UnopenableError::GameOver),
+	}
+}
+
 impl<B: BaseGameBoard> BaseGameBoard for LazyGameBoard<B> {
 	fn with_clearing(
 		x: u16,
@@ -56,11 +77,29 @@ impl<B: BaseGameBoard> BaseGameBoard for LazyGameBoard<B> {
 		clearx: u16,
 		cleary: u16,
 	) -> Result<Self, NewBoardError> {
-		Ok(LazyGameBoard(Init(B::with_clearing(
-			x, y, bombs, clearx, cleary,
+		Ok(Self(Init(B::with_clearing(x, y, bombs, clearx, cleary)?)))
+	}
+
+	fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Ok(Self(Init(B::with_clearing_seeded(
+			x, y, bombs, clearx, cleary, seed,
 		)?)))
 	}
 
+	/// returns the board to being uninit, so the next move generates the board around it
+	fn reset_with(&mut self, x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
+		*self = Self::new_uninit(x, y, bombs)?;
+
+		Ok(())
+	}
+
 	fn flagged(&self) -> u32 {
 		match self.0 {
 			Init(ref board) => board.flagged(),
@@ -101,6 +140,57 @@ impl<B: BaseGameBoard> BaseGameBoard for LazyGameBoard<B> {
 		}
 	}
 
+	fn state(&self) -> GameState {
+		match self.0 {
+			Init(ref board) => board.state(),
+			Uninit { .. } => GameState::Playing,
+		}
+	}
+
+	fn layout_3bv(&self) -> Option<u32> {
+		match self.0 {
+			Init(ref board) => board.layout_3bv(),
+			Uninit { .. } => None,
+		}
+	}
+
+	fn annotation(&self, x: u16, y: u16) -> Option<char> {
+		match self.0 {
+			Init(ref board) => board.annotation(x, y),
+			Uninit { .. } => None,
+		}
+	}
+
+	fn chord_policy(&self) -> ChordPolicy {
+		match self.0 {
+			Init(ref board) => board.chord_policy(),
+			Uninit { .. } => ChordPolicy::default(),
+		}
+	}
+
+	fn snapshot(&self) -> BoardSnapshot {
+		BoardSnapshot::new(match self.0 {
+			Init(ref board) => LazySnapshot::Init(board.snapshot()),
+			Uninit { x, y, bombs } => LazySnapshot::Uninit { x, y, bombs },
+		})
+	}
+
+	/// restores like [`BaseGameBoard::restore`], where a snapshot of an init board can not be restored onto an uninit board,
+	/// as there is no board to restore it into, which returns [`RestoreError::Unsupported`]
+	fn restore(&mut self, snapshot: &BoardSnapshot) -> Result<(), RestoreError> {
+		match (
+			&mut self.0,
+			snapshot.downcast_ref().ok_or(RestoreError::WrongBoard)?,
+		) {
+			(Init(board), LazySnapshot::Init(inner)) => board.restore(inner),
+			(Uninit { .. }, LazySnapshot::Init(_)) => Err(RestoreError::Unsupported),
+			(_, &LazySnapshot::Uninit { x, y, bombs }) => {
+				self.0 = Uninit { x, y, bombs };
+				Ok(())
+			}
+		}
+	}
+
 	fn undo_move(&mut self, ge: &GameBoardEvent) -> Result<(), UndoError> {
 		match self.0 {
 			Init(ref mut board) => board.undo_move(ge),
@@ -125,13 +215,9 @@ impl<B: BaseGameBoard> BaseGameBoard for LazyGameBoard<B> {
 	fn get_board_tile(&self, posx: u16, posy: u16) -> Option<VisibleTile> {
 		match self.0 {
 			Init(ref board) => board.get_board_tile(posx, posy),
-			Uninit { x, y, .. } => {
-				if (posx < x) && (posy < y) {
-					Some(VisibleTile::NotVisible)
-				} else {
-					None
-				}
-			}
+			Uninit { .. } => self
+				.in_bounds(posx, posy)
+				.then_some(VisibleTile::NotVisible),
 		}
 	}
 
@@ -144,36 +230,71 @@ impl<B: BaseGameBoard> BaseGameBoard for LazyGameBoard<B> {
 
 	fn do_event(&mut self, ge: KeyEvent) -> Result<(), UnopenableError> {
 		match self.0 {
-			Init(ref mut board) => board.do_event(ge),
+			Init(ref mut board) => {
+				if matches!(ge, KeyEvent::Mouse1(..) | KeyEvent::Mouse2(..)) {
+					assert_playing(board)?;
+				}
+
+				board.do_event(ge)
+			}
 			Uninit { .. } => BaseGameBoard_do_event(self, ge),
 		}
 	}
 }
 
-impl<B: BaseGameBoard> LazyGameBoard<B> {
-	fn init_with_mut(&mut self, clearx: u16, cleary: u16) -> &mut B {
-		match self.0 {
-			Init(ref mut board) => return board,
-			Uninit { x, y, bombs } => {
-				let board = B::with_clearing(x, y, bombs, clearx, cleary).unwrap();
-				self.0 = Init(board);
-			}
-		}
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn moves_after_a_loss_are_game_over() {
+		let layout = GameBoard::from_board_format("100\n000\n001").unwrap();
+		let mut gb = LazyGameBoard(Init(layout));
+
+		assert!(matches!(
+			gb.open_tile(0, 0),
+			Err(UnopenableError::BombHit(0, 0))
+		));
+		gb.lose_game();
 
-		self.must_init_mut()
+		assert!(matches!(gb.open_tile(2, 0), Err(UnopenableError::GameOver)));
+		assert!(matches!(gb.flag_tile(2, 2), Err(UnopenableError::GameOver)));
+		assert_eq!(gb.opened(), 0);
 	}
 
-	fn must_init_mut(&mut self) -> &mut B {
-		match self.0 {
-			Init(ref mut board) => board,
-			Uninit { .. } => unreachable!(),
-		}
+	#[test]
+	fn uninit_board_is_neither_won_nor_lost() {
+		let gb = LazyGameBoard::<GameBoard>::new_uninit(9, 9, 10).unwrap();
+		assert!(!gb.is_won() && !gb.is_lost());
+
+		let mut gb = LazyGameBoard(Init(GameBoard::from_board_format("100\n000\n001").unwrap()));
+		gb.open_tile(2, 0).unwrap();
+		gb.open_tile(0, 2).unwrap();
+		assert!(gb.is_won() && !gb.is_lost());
 	}
 
-	fn must_init(&self) -> &B {
-		match self.0 {
-			Init(ref board) => board,
-			Uninit { .. } => unreachable!(),
-		}
+	#[test]
+	fn failed_generation_is_an_error_and_stays_uninit() {
+		// a clearing needs 9 safe tiles, which a 3x3 board with bombs does not have
+		let mut gb = LazyGameBoard::<GameBoard>::new_uninit(3, 3, 2).unwrap();
+
+		assert!(matches!(
+			gb.open_tile(1, 1),
+			Err(UnopenableError::GenerationFailed(
+				NewBoardError::BombOverflow
+			))
+		));
+		assert!(matches!(
+			gb.flag_tile(0, 0),
+			Err(UnopenableError::GenerationFailed(
+				NewBoardError::BombOverflow
+			))
+		));
+		assert!(matches!(gb.0, Uninit { .. }));
+
+		// a board with room for the clearing still generates on the next move
+		gb.reset_with(3, 3, 0).unwrap();
+		gb.open_tile(1, 1).unwrap();
+		assert!(matches!(gb.0, Init(_)));
 	}
 }
@@ -0,0 +1,30 @@
+//! minesweeper game logic, solver and ui
+//!
+//! the [gameboard] module defines the [`BaseGameBoard`][gameboard::BaseGameBoard] trait and its core implementation,
+//! which the wrappers in [lazy], [logged] and [sync] build on, and which [ui] renders for a cursive user interface
+
+#![warn(clippy::nursery)]
+#![warn(clippy::pedantic)]
+#![warn(clippy::cargo)]
+// the duplicates come from the dependency trees of cursive and rand, which this crate can not unify
+#![allow(clippy::multiple_crate_versions)]
+// errors and panics are described in the prose of each item instead of in rustdoc sections
+#![allow(clippy::missing_errors_doc, clippy::missing_panics_doc)]
+// x/y pairs such as clearx and cleary are named alike on purpose
+#![allow(clippy::similar_names)]
+
+mod diag;
+pub mod gameboard;
+#[cfg(feature = "headless")]
+pub mod headless;
+#[cfg(feature = "headless")]
+mod input;
+pub mod lazy;
+pub mod logged;
+#[cfg(any(test, feature = "test-util"))]
+pub mod mock;
+pub mod replay;
+pub mod solver;
+pub mod sparse;
+pub mod sync;
+pub mod ui;
@@ -1,18 +1,31 @@
 //! a logged game board implementation that stores every move
 //!
-//! defines the [LoggedGameBoard] structure for logging which accepts a [BaseGameBoard] and consumes do_event calls to a logger
+//! defines the [`LoggedGameBoard`] structure for logging which accepts a [`BaseGameBoard`] and consumes `do_event` calls to a logger
+
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::time::Duration;
+use std::{fs, io};
 
 use super::gameboard;
 
 use gameboard::{
-	BaseGameBoard, FlatBoard, GameBoardEvent, KeyEvent, NewBoardError, UndoError, UnopenableError,
-	VisibleTile,
+	BaseGameBoard, BoardSnapshot, ChordPolicy, FlatBoard, GameBoard, GameBoardEvent, GameState,
+	KeyEvent, NewBoardError, RestoreError, UndoError, UnopenableError, VisibleTile,
 };
 
+use rand::{thread_rng, Rng};
+
+use crate::diag::log_warn;
+use crate::replay::{ReplayBundle, SerializableFrame, REPLAY_BUNDLE_VERSION};
+
 /// internally stored keyevent that also stores any effect it had on the gameboard
+#[derive(Clone)]
 enum KeyEventEffect {
 	Mouse1(u16, u16, GameBoardEvent),
 	Mouse2(u16, u16, GameBoardEvent),
+	/// a mouse press that hit a bomb and lost the game, which has no event to undo
+	BombHit(KeyEvent),
 	Pause,
 	UnPause,
 	Idle,
@@ -27,28 +40,135 @@ impl TryFrom<KeyEvent> for KeyEventEffect {
 	fn try_from(k: KeyEvent) -> Result<Self, Self::Error> {
 		match k {
 			KeyEvent::Mouse1(_, _) | KeyEvent::Mouse2(_, _) => Err(RequiresGameBoardEvent),
-			KeyEvent::Pause => Ok(KeyEventEffect::Pause),
-			KeyEvent::UnPause => Ok(KeyEventEffect::UnPause),
-			KeyEvent::Idle => Ok(KeyEventEffect::Idle),
+			KeyEvent::Pause => Ok(Self::Pause),
+			KeyEvent::UnPause => Ok(Self::UnPause),
+			KeyEvent::Idle => Ok(Self::Idle),
 		}
 	}
 }
 
+#[derive(Clone)]
 struct LogFrame {
 	time_offset_micros: u64,
 	trace: KeyEventEffect,
 }
 
+/// the kind of a logged frame, borrowing the effect of a move from the log
+#[derive(Copy, Clone, Debug)]
+pub enum LoggedFrameKind<'a> {
+	/// a mouse 1 press on x/y and the event it caused
+	Mouse1(u16, u16, &'a GameBoardEvent),
+	/// a mouse 2 press on x/y and the event it caused
+	Mouse2(u16, u16, &'a GameBoardEvent),
+	/// a mouse press that hit a bomb and lost the game
+	BombHit(KeyEvent),
+	Pause,
+	UnPause,
+	Idle,
+}
+
+/// a read only view of a logged frame
+#[derive(Copy, Clone, Debug)]
+pub struct LoggedFrameView<'a> {
+	/// the time since the game started
+	pub time_offset: Duration,
+	pub kind: LoggedFrameKind<'a>,
+}
+
+impl<'a> From<&'a LogFrame> for LoggedFrameView<'a> {
+	fn from(frame: &'a LogFrame) -> Self {
+		let kind = match frame.trace {
+			KeyEventEffect::Mouse1(x, y, ref event) => LoggedFrameKind::Mouse1(x, y, event),
+			KeyEventEffect::Mouse2(x, y, ref event) => LoggedFrameKind::Mouse2(x, y, event),
+			KeyEventEffect::BombHit(k) => LoggedFrameKind::BombHit(k),
+			KeyEventEffect::Pause => LoggedFrameKind::Pause,
+			KeyEventEffect::UnPause => LoggedFrameKind::UnPause,
+			KeyEventEffect::Idle => LoggedFrameKind::Idle,
+		};
+
+		Self {
+			time_offset: Duration::from_micros(frame.time_offset_micros),
+			kind,
+		}
+	}
+}
+
+/// the state of a [`LoggedGameBoard`] captured by [`BaseGameBoard::snapshot`], the game timer keeps running across a restore
+struct LoggedSnapshot {
+	board: BoardSnapshot,
+	events: Vec<LogFrame>,
+	end_mono: Option<time::Instant>,
+}
+
+/// periodic saving of the board, set with [`LoggedGameBoard::enable_autosave`]
+struct Autosave<GB> {
+	path: PathBuf,
+	interval: Duration,
+	/// the [`LoggedGameBoard::elapsed`] time of the last save
+	last_save: Duration,
+	snapshot: fn(&GB) -> Vec<u8>,
+}
+
 pub struct LoggedGameBoard<GB: BaseGameBoard> {
 	start_time: time::OffsetDateTime,
 	start_mono: time::Instant,
+	/// when the game was won or lost, freezing the game timer
+	end_mono: Option<time::Instant>,
 
 	board: GB,
 
 	events: Vec<LogFrame>,
+
+	autosave: Option<Autosave<GB>>,
+}
+
+impl LoggedGameBoard<GameBoard> {
+	/// saves a [`GameBoard::save_snapshot`] of the board to path every interval of game time, checked after every [`BaseGameBoard::do_event`]
+	///
+	/// saving happens on the calling thread rather than in the background, and replaces the file atomically so a crash mid save keeps the last save
+	pub fn enable_autosave(&mut self, path: impl Into<PathBuf>, interval: Duration) {
+		self.autosave = Some(Autosave {
+			path: path.into(),
+			interval,
+			last_save: self.elapsed(),
+			snapshot: GameBoard::save_snapshot,
+		});
+	}
+
+	/// bundles the board configuration, seed, clearing and every logged input into a [`ReplayBundle`]
+	#[must_use]
+	pub fn to_replay_bundle(&self) -> ReplayBundle {
+		let frames = self
+			.events
+			.iter()
+			.map(|frame| SerializableFrame {
+				time_offset_micros: frame.time_offset_micros,
+				event: match frame.trace {
+					KeyEventEffect::Mouse1(x, y, _) => KeyEvent::Mouse1(x, y),
+					KeyEventEffect::Mouse2(x, y, _) => KeyEvent::Mouse2(x, y),
+					KeyEventEffect::BombHit(k) => k,
+					KeyEventEffect::Pause => KeyEvent::Pause,
+					KeyEventEffect::UnPause => KeyEvent::UnPause,
+					KeyEventEffect::Idle => KeyEvent::Idle,
+				},
+			})
+			.collect();
+
+		ReplayBundle {
+			version: REPLAY_BUNDLE_VERSION,
+			dimensions: self.board.dimensions(),
+			bombs: self.board.bomb_count(),
+			seed: self.board.seed(),
+			clearing: self.board.clearing(),
+			frames,
+		}
+	}
 }
 
 impl<T: BaseGameBoard> LoggedGameBoard<T> {
+	/// generates a board with a clearing at opening x/y from a random seed and logs opening it
+	///
+	/// the seed is drawn here rather than by the board, so a [`GameBoard`] can be regenerated from its [`LoggedGameBoard::to_replay_bundle`]
 	pub fn start_new(
 		x: u16,
 		y: u16,
@@ -56,28 +176,170 @@ impl<T: BaseGameBoard> LoggedGameBoard<T> {
 		opening_x: u16,
 		opening_y: u16,
 	) -> Result<Self, NewBoardError> {
+		Self::start_new_seeded(x, y, bombs, opening_x, opening_y, thread_rng().gen())
+	}
+
+	/// generates a board with a clearing at opening x/y through [`BaseGameBoard::with_clearing_seeded`] and logs opening it
+	pub fn start_new_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		opening_x: u16,
+		opening_y: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		let board = T::with_clearing_seeded(x, y, bombs, opening_x, opening_y, seed)?;
+
+		Self::start_with(board, opening_x, opening_y)
+	}
+
+	/// wraps a freshly generated board and logs opening x/y on it, returning [`NewBoardError::InitialOpenFailed`] if the opening fails
+	fn start_with(board: T, opening_x: u16, opening_y: u16) -> Result<Self, NewBoardError> {
 		let mut board = Self {
 			start_time: time::OffsetDateTime::now_utc(),
 			start_mono: time::Instant::now(),
-			board: T::with_clearing(x, y, bombs, opening_x, opening_y)?,
+			end_mono: None,
+			board,
 			events: vec![],
+			autosave: None,
 		};
 
+		// GameBoard::with_clearing guarantees the clearing opens, but other implementations may not
+		let event = board
+			.board
+			.open_tile(opening_x, opening_y)
+			.map_err(|_| NewBoardError::InitialOpenFailed)?;
+
 		board.events.push(LogFrame {
-			// SAFETY: GameBoard::with_clearing guarantees that clearx and cleary are an empty square, so no bomb is possible
-			// bounds are checked via with_clearing validating bounds on clearx and cleary
-			// cell is not opened/flagged because we just created a new board
-			trace: KeyEventEffect::Mouse1(
-				x,
-				y,
-				board.board.open_tile(opening_x, opening_y).unwrap(),
-			),
+			trace: KeyEventEffect::Mouse1(opening_x, opening_y, event),
 			time_offset_micros: board.current_micros_offset(),
 		});
+		board.stop_timer_if_over();
 
 		Ok(board)
 	}
 
+	/// returns a view of every logged frame in order, starting with the opening move
+	pub fn frames(&self) -> impl Iterator<Item = LoggedFrameView<'_>> {
+		self.events.iter().map(LoggedFrameView::from)
+	}
+
+	/// returns every logged move with the time since the previous move, or since the game started for the first move
+	///
+	/// only mouse events change the board, so pause and idle frames are left out and their time is folded into the gap before the next move
+	pub fn moves_with_timing(&self) -> Vec<(Duration, GameBoardEvent)> {
+		let mut last_offset = 0u64;
+
+		self.events
+			.iter()
+			.filter_map(|frame| {
+				let event = match frame.trace {
+					KeyEventEffect::Mouse1(_, _, ref event)
+					| KeyEventEffect::Mouse2(_, _, ref event) => event,
+					KeyEventEffect::BombHit(_)
+					| KeyEventEffect::Pause
+					| KeyEventEffect::UnPause
+					| KeyEventEffect::Idle => return None,
+				};
+
+				let gap = frame.time_offset_micros.saturating_sub(last_offset);
+				last_offset = frame.time_offset_micros;
+
+				Some((Duration::from_micros(gap), event.clone()))
+			})
+			.collect()
+	}
+
+	/// returns the time since the game started, which stops advancing once the game is won or lost
+	pub fn elapsed(&self) -> Duration {
+		let end = self.end_mono.unwrap_or_else(time::Instant::now);
+
+		(end - self.start_mono).try_into().unwrap_or_default()
+	}
+
+	/// stops saving the board, keeping the last save on disk
+	pub fn disable_autosave(&mut self) {
+		self.autosave = None;
+	}
+
+	/// saves the board if autosave is enabled and an interval of game time passed since the last save, and returns whether it saved
+	///
+	/// the snapshot is written to a temporary file next to the save, which is then renamed over it
+	pub fn autosave_if_due(&mut self) -> io::Result<bool> {
+		let elapsed = self.elapsed();
+
+		let Some(autosave) = &mut self.autosave else {
+			return Ok(false);
+		};
+
+		if elapsed.saturating_sub(autosave.last_save) < autosave.interval {
+			return Ok(false);
+		}
+
+		let mut tmp = OsString::from(autosave.path.as_os_str());
+		tmp.push(".tmp");
+
+		fs::write(&tmp, (autosave.snapshot)(&self.board))?;
+		fs::rename(&tmp, &autosave.path)?;
+
+		autosave.last_save = elapsed;
+
+		Ok(true)
+	}
+
+	/// applies k to the board and logs it with its effect
+	///
+	/// failed mouse events are not logged, except for a bomb hit which is logged as the move that lost the game
+	fn log_event(&mut self, k: KeyEvent) -> Result<(), UnopenableError> {
+		self.log_effect(k).inspect_err(|err| {
+			if matches!(err, UnopenableError::BombHit(..)) {
+				self.events.push(LogFrame {
+					trace: KeyEventEffect::BombHit(k),
+					time_offset_micros: self.current_micros_offset(),
+				});
+			}
+		})
+	}
+
+	/// applies k to the board and logs it if it succeeded
+	fn log_effect(&mut self, k: KeyEvent) -> Result<(), UnopenableError> {
+		use KeyEvent::{Mouse1, Mouse2};
+		match k {
+			Mouse1(x, y) => {
+				let event = self.board.open_or_chord(x, y)?;
+
+				self.events.push(LogFrame {
+					trace: KeyEventEffect::Mouse1(x, y, event),
+					time_offset_micros: self.current_micros_offset(),
+				});
+			}
+			Mouse2(x, y) => {
+				// right clicks that change nothing are not logged, like failed left clicks
+				if let Some(event) = self.board.flag_or_chord(x, y)? {
+					self.events.push(LogFrame {
+						trace: KeyEventEffect::Mouse2(x, y, event),
+						time_offset_micros: self.current_micros_offset(),
+					});
+				}
+			}
+			v => self.events.push(LogFrame {
+				trace: v
+					.try_into()
+					.expect("Impossible invariant (KeyEvent Mouse1 and Mouse2 already handled)"),
+				time_offset_micros: self.current_micros_offset(),
+			}),
+		}
+
+		Ok(())
+	}
+
+	/// freezes the game timer if the game has ended and it is not frozen yet
+	fn stop_timer_if_over(&mut self) {
+		if self.end_mono.is_none() && self.board.state() != GameState::Playing {
+			self.end_mono = Some(time::Instant::now());
+		}
+	}
+
 	fn current_micros_offset(&self) -> u64 {
 		self.start_mono.elapsed().whole_microseconds().try_into().expect("Game timer exceeded 64 bit limit of microseconds (exceeding 200_000 years since game start)")
 	}
@@ -102,26 +364,87 @@ impl<T: BaseGameBoard> BaseGameBoard for LoggedGameBoard<T> {
 		Self::start_new(x, y, bombs, clearx, cleary)
 	}
 
+	fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		Self::start_new_seeded(x, y, bombs, clearx, cleary, seed)
+	}
+
+	/// resets the wrapped board and clears the log and game timer, without logging an opening move
+	fn reset_with(&mut self, x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
+		self.board.reset_with(x, y, bombs)?;
+
+		self.start_time = time::OffsetDateTime::now_utc();
+		self.start_mono = time::Instant::now();
+		self.end_mono = None;
+		self.events.clear();
+
+		if let Some(autosave) = &mut self.autosave {
+			autosave.last_save = Duration::ZERO;
+		}
+
+		Ok(())
+	}
+
 	impl_from_board!(dimensions, (u16, u16));
 	impl_from_board!(bomb_count, u32);
 	impl_from_board!(flagged, u32);
 	impl_from_board!(opened, u32);
 	impl_from_board!(render, FlatBoard<VisibleTile>);
+	impl_from_board!(state, GameState);
+	impl_from_board!(layout_3bv, Option<u32>);
+
+	fn annotation(&self, x: u16, y: u16) -> Option<char> {
+		self.board.annotation(x, y)
+	}
+
+	fn chord_policy(&self) -> ChordPolicy {
+		self.board.chord_policy()
+	}
+
+	fn snapshot(&self) -> BoardSnapshot {
+		BoardSnapshot::new(LoggedSnapshot {
+			board: self.board.snapshot(),
+			events: self.events.clone(),
+			end_mono: self.end_mono,
+		})
+	}
+
+	fn restore(&mut self, snapshot: &BoardSnapshot) -> Result<(), RestoreError> {
+		let snapshot: &LoggedSnapshot = snapshot.downcast_ref().ok_or(RestoreError::WrongBoard)?;
+
+		self.board.restore(&snapshot.board)?;
+		self.events.clone_from(&snapshot.events);
+		self.end_mono = snapshot.end_mono;
+
+		Ok(())
+	}
 
 	fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
 		self.board.get_board_tile(x, y)
 	}
 
 	fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.board.open_around(x, y)
+		let res = self.board.open_around(x, y);
+		self.stop_timer_if_over();
+		res
 	}
 
 	fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.board.open_tile(x, y)
+		let res = self.board.open_tile(x, y);
+		self.stop_timer_if_over();
+		res
 	}
 
 	fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
-		self.board.flag_tile(x, y)
+		let res = self.board.flag_tile(x, y);
+		self.stop_timer_if_over();
+		res
 	}
 
 	fn undo_move(&mut self, f: &GameBoardEvent) -> Result<(), UndoError> {
@@ -129,61 +452,292 @@ impl<T: BaseGameBoard> BaseGameBoard for LoggedGameBoard<T> {
 	}
 
 	fn win_game(&mut self) -> Result<(), u32> {
-		self.board.win_game()
+		let res = self.board.win_game();
+		self.stop_timer_if_over();
+		res
 	}
 
 	fn lose_game(&mut self) {
-		self.board.lose_game()
+		self.board.lose_game();
+		self.stop_timer_if_over();
 	}
 
 	fn do_event(&mut self, k: KeyEvent) -> Result<(), UnopenableError> {
-		{
-			use KeyEvent::{Mouse1, Mouse2};
-			match k {
-				Mouse1(x, y) => {
-					let tile = self
-						.get_board_tile(x, y)
-						.ok_or(UnopenableError::OutOfBounds)?;
-					let event: GameBoardEvent = match tile {
-						VisibleTile::NotVisible => self.board.open_tile(x, y)?,
-						VisibleTile::Visible(_) => self.board.open_around(x, y)?,
-						VisibleTile::Flagged => {
-							return Err(UnopenableError::FlaggedTile);
-						}
-					};
+		let res = self.log_event(k);
 
-					self.events.push(LogFrame {
-						trace: KeyEventEffect::Mouse1(x, y, event),
-						time_offset_micros: self.current_micros_offset(),
-					});
-				}
-				Mouse2(x, y) => {
-					let tile = self
-						.get_board_tile(x, y)
-						.ok_or(UnopenableError::OutOfBounds)?;
-					match tile {
-						VisibleTile::NotVisible | VisibleTile::Flagged => {
-							self.board.flag_tile(x, y)?
-						}
-						VisibleTile::Visible(_) => {
-							return Err(UnopenableError::AlreadyOpen);
-						}
-					};
+		// a bomb hit fails the move but still ends the game
+		self.stop_timer_if_over();
 
-					self.events.push(LogFrame {
-						trace: KeyEventEffect::Mouse2(x, y, GameBoardEvent::flag_tile(x, y)),
-						time_offset_micros: self.current_micros_offset(),
-					});
-				}
-				v => self.events.push(LogFrame {
-					trace: v.try_into().expect(
-						"Impossible invariant (KeyEvent Mouse1 and Mouse2 already handled)",
-					),
-					time_offset_micros: self.current_micros_offset(),
-				}),
-			};
-		}
+		res.inspect_err(|err| log_warn!("invalid move {k:?}: {err}"))?;
+
+		// a failed autosave should not fail the move, the next move retries it
+		let _ = self.autosave_if_due();
 
 		Ok(())
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	/// wraps a board in a logger without playing an opening move
+	fn logged<B: BaseGameBoard>(board: B) -> LoggedGameBoard<B> {
+		LoggedGameBoard {
+			start_time: time::OffsetDateTime::now_utc(),
+			start_mono: time::Instant::now(),
+			end_mono: None,
+			board,
+			events: vec![],
+			autosave: None,
+		}
+	}
+
+	fn layout() -> GameBoard {
+		GameBoard::from_board_format("100\n000\n001").unwrap()
+	}
+
+	#[test]
+	fn moves_with_timing_folds_pauses_into_the_next_gap() {
+		let mut gb = logged(layout());
+
+		gb.do_event(KeyEvent::Mouse1(2, 0)).unwrap();
+		gb.do_event(KeyEvent::Pause).unwrap();
+		gb.do_event(KeyEvent::Mouse2(0, 0)).unwrap();
+
+		let moves = gb.moves_with_timing();
+		let events: Vec<_> = moves.iter().map(|(_, event)| event.clone()).collect();
+
+		assert_eq!(events.len(), 2);
+		assert_eq!(events[1], GameBoardEvent::flag_tile(0, 0));
+
+		let last = gb.frames().last().unwrap().time_offset;
+		assert_eq!(moves.iter().map(|&(gap, _)| gap).sum::<Duration>(), last);
+	}
+
+	#[test]
+	fn elapsed_stops_once_the_game_is_over() {
+		let mut gb = logged(layout());
+
+		let first = gb.elapsed();
+		gb.do_event(KeyEvent::Mouse1(2, 0)).unwrap();
+		assert!(gb.elapsed() >= first);
+
+		gb.do_event(KeyEvent::Mouse1(0, 2)).unwrap();
+		assert_eq!(gb.state(), GameState::Won);
+
+		let end = gb.elapsed();
+		std::thread::sleep(Duration::from_millis(2));
+		assert_eq!(gb.elapsed(), end);
+
+		// moves made through the trait instead of do_event stop the timer as well
+		let mut gb = logged(layout());
+
+		gb.open_tile(2, 0).unwrap();
+		gb.open_tile(0, 2).unwrap();
+		assert_eq!(gb.state(), GameState::Won);
+
+		let end = gb.elapsed();
+		std::thread::sleep(Duration::from_millis(2));
+		assert_eq!(gb.elapsed(), end);
+
+		let mut gb = logged(layout());
+
+		assert!(gb.open_tile(2, 2).is_err());
+		assert_eq!(gb.state(), GameState::Lost);
+
+		let end = gb.elapsed();
+		std::thread::sleep(Duration::from_millis(2));
+		assert_eq!(gb.elapsed(), end);
+	}
+
+	#[test]
+	fn frames_borrow_every_logged_frame_in_order() {
+		let mut gb = logged(layout());
+
+		gb.do_event(KeyEvent::Mouse1(2, 0)).unwrap();
+		gb.do_event(KeyEvent::Pause).unwrap();
+		gb.do_event(KeyEvent::UnPause).unwrap();
+
+		let frames: Vec<_> = gb.frames().collect();
+		assert_eq!(frames.len(), 3);
+		assert!(matches!(frames[0].kind, LoggedFrameKind::Mouse1(2, 0, _)));
+		assert!(matches!(frames[1].kind, LoggedFrameKind::Pause));
+		assert!(matches!(frames[2].kind, LoggedFrameKind::UnPause));
+		assert!(frames
+			.windows(2)
+			.all(|w| w[0].time_offset <= w[1].time_offset));
+
+		// the board is still usable after viewing
+		assert_eq!(gb.frames().count(), 3);
+	}
+
+	/// plays a board to the end by opening every hidden tile until a bomb is hit, which is logged as the last frame
+	fn lose(gb: &mut LoggedGameBoard<GameBoard>) {
+		let layout: Vec<Vec<char>> = gb
+			.board
+			.to_board_format()
+			.lines()
+			.map(|row| row.chars().collect())
+			.collect();
+		let (x, y) = gb
+			.board
+			.y_range()
+			.flat_map(|y| gb.board.x_range().map(move |x| (x, y)))
+			.find(|&(x, y)| layout[usize::from(y)][usize::from(x)] == '1')
+			.unwrap();
+
+		let hit = gb.do_event(KeyEvent::Mouse1(x, y));
+
+		assert!(matches!(hit, Err(UnopenableError::BombHit(..))));
+		assert_eq!(gb.state(), GameState::Lost);
+		assert!(matches!(
+			gb.frames().last().unwrap().kind,
+			LoggedFrameKind::BombHit(KeyEvent::Mouse1(..))
+		));
+	}
+
+	#[test]
+	fn started_boards_are_seeded_and_replay_to_the_same_board() {
+		let mut gb = LoggedGameBoard::<GameBoard>::start_new(9, 9, 10, 4, 4).unwrap();
+		assert!(gb.board.seed().is_some());
+
+		let replayed = crate::replay::replay_bundle_to_board(&gb.to_replay_bundle()).unwrap();
+		assert_eq!(replayed.render(), gb.render());
+
+		lose(&mut gb);
+
+		let bundle = gb.to_replay_bundle();
+		let replayed = crate::replay::replay_bundle_to_board(&bundle).unwrap();
+
+		assert_eq!(replayed.state(), GameState::Lost);
+		assert_eq!(replayed.render(), gb.render());
+		assert_eq!(gb.moves_with_timing().len(), bundle.frames.len() - 1);
+	}
+
+	#[test]
+	fn seeded_starts_repeat_the_layout() {
+		let a = LoggedGameBoard::<GameBoard>::start_new_seeded(9, 9, 10, 4, 4, 7).unwrap();
+		let b = LoggedGameBoard::<GameBoard>::start_new_seeded(9, 9, 10, 4, 4, 7).unwrap();
+
+		assert_eq!(a.board.seed(), Some(7));
+		assert_eq!(a.board.to_board_format(), b.board.to_board_format());
+	}
+
+	#[cfg(feature = "serde")]
+	#[test]
+	fn replay_bundles_round_trip_through_serde() {
+		let mut gb = LoggedGameBoard::<GameBoard>::start_new_seeded(9, 9, 10, 4, 4, 3).unwrap();
+		gb.do_event(KeyEvent::Pause).unwrap();
+		gb.do_event(KeyEvent::UnPause).unwrap();
+		lose(&mut gb);
+
+		let bundle = gb.to_replay_bundle();
+		let json = serde_json::to_string(&bundle).unwrap();
+		let decoded: ReplayBundle = serde_json::from_str(&json).unwrap();
+
+		assert_eq!(decoded, bundle);
+		assert_eq!(
+			crate::replay::replay_bundle_to_board(&decoded)
+				.unwrap()
+				.render(),
+			gb.render()
+		);
+	}
+
+	#[test]
+	fn autosave_writes_a_snapshot_once_the_interval_passed() {
+		let path = std::env::temp_dir().join(format!("aisweeper-autosave-{}", std::process::id()));
+		let _ = fs::remove_file(&path);
+
+		let mut gb = logged(layout());
+		gb.enable_autosave(&path, Duration::from_hours(1));
+
+		gb.do_event(KeyEvent::Mouse1(2, 0)).unwrap();
+		assert!(!gb.autosave_if_due().unwrap());
+		assert!(!path.exists());
+
+		// with no interval every move is due
+		gb.enable_autosave(&path, Duration::ZERO);
+		gb.do_event(KeyEvent::Mouse2(0, 0)).unwrap();
+
+		let saved = GameBoard::load_snapshot(&fs::read(&path).unwrap()).unwrap();
+		assert_eq!(saved.render(), gb.render());
+		assert!(!path.with_extension("tmp").exists());
+
+		gb.disable_autosave();
+		fs::remove_file(&path).unwrap();
+	}
+
+	#[test]
+	fn failed_opening_moves_are_a_new_board_error() {
+		use crate::mock::{MockCall, MockGameBoard};
+
+		let mut mock = MockGameBoard::new(FlatBoard::new(3, 3, VisibleTile::NotVisible), 1);
+		mock.push_response(Err(UnopenableError::BombHit(1, 1)));
+
+		assert!(matches!(
+			LoggedGameBoard::start_with(mock, 1, 1),
+			Err(NewBoardError::InitialOpenFailed)
+		));
+
+		// a successful opening is logged as the first frame
+		let started = LoggedGameBoard::<MockGameBoard>::start_new(3, 3, 0, 1, 1).unwrap();
+		assert_eq!(started.board.calls(), [MockCall::OpenTile(1, 1)]);
+		assert!(matches!(
+			started.frames().next().unwrap().kind,
+			LoggedFrameKind::Mouse1(1, 1, _)
+		));
+	}
+
+	#[test]
+	fn reset_clears_the_log_and_every_wrapped_layer() {
+		use crate::lazy::LazyGameBoard;
+		use crate::sync::SyncGameBoard;
+
+		let lazy = LazyGameBoard::<GameBoard>::new_uninit(9, 9, 30).unwrap();
+		let mut gb = logged(SyncGameBoard::new(lazy));
+
+		gb.do_event(KeyEvent::Mouse1(0, 0)).unwrap();
+		let (x, y) = gb.frontier().next().unwrap();
+		gb.do_event(KeyEvent::Mouse2(x, y)).unwrap();
+		assert!(gb.opened() > 0);
+		assert_eq!(gb.flagged(), 1);
+
+		gb.reset_with(16, 16, 40).unwrap();
+
+		assert_eq!(gb.dimensions(), (16, 16));
+		assert_eq!(gb.bomb_count(), 40);
+		assert_eq!((gb.opened(), gb.flagged()), (0, 0));
+		assert_eq!(gb.state(), GameState::Playing);
+		assert_eq!(gb.frames().count(), 0);
+		assert!(gb.elapsed() < Duration::from_secs(1));
+
+		// the lazy layer is uninit again, so any first click is safe
+		gb.do_event(KeyEvent::Mouse1(15, 15)).unwrap();
+		assert!(gb.opened() > 0);
+		assert_eq!(gb.frames().count(), 1);
+	}
+
+	#[test]
+	fn restore_reverts_the_log_with_the_board() {
+		let mut gb = logged(layout());
+		gb.do_event(KeyEvent::Mouse1(2, 0)).unwrap();
+
+		let snapshot = gb.snapshot();
+		let render = gb.render();
+
+		gb.do_event(KeyEvent::Mouse2(0, 0)).unwrap();
+		gb.do_event(KeyEvent::Mouse2(2, 2)).unwrap();
+		assert_eq!(gb.frames().count(), 3);
+
+		gb.restore(&snapshot).unwrap();
+
+		assert_eq!(gb.render(), render);
+		assert_eq!(gb.frames().count(), 1);
+		assert!(matches!(
+			gb.restore(&layout().snapshot()),
+			Err(RestoreError::WrongBoard)
+		));
+	}
+}
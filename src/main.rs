@@ -3,33 +3,53 @@
 #![warn(clippy::nursery)]
 #![warn(clippy::pedantic)]
 #![warn(clippy::cargo)]
-mod gameboard;
-mod lazy;
-mod logged;
-mod ui;
+#![allow(clippy::multiple_crate_versions)]
 
-use gameboard::GameBoard;
-use lazy::LazyGameBoard;
-use logged::LoggedGameBoard;
-use ui::MineGameView;
+use aisweeper::gameboard::GameBoard;
+#[cfg(feature = "headless")]
+use aisweeper::headless;
+use aisweeper::lazy::LazyGameBoard;
+use aisweeper::logged::LoggedGameBoard;
+use aisweeper::ui::MineGameView;
 
-use cursive;
 use cursive::crossterm;
+use cursive::view::Nameable;
 use cursive::Cursive;
 
+type GameView = MineGameView<LazyGameBoard<LoggedGameBoard<GameBoard>>>;
+
+const GAME_VIEW: &str = "game";
+
 fn main() {
+	#[cfg(feature = "headless")]
+	if std::env::args().any(|arg| arg == "--headless") {
+		let mut board = LazyGameBoard::<GameBoard>::new_uninit(16, 16, 40).unwrap();
+		headless::run(&mut board);
+		return;
+	}
+
 	cursive::logger::init();
 
 	let mut cursive = crossterm();
 
-	let view: MineGameView<LazyGameBoard<LoggedGameBoard<GameBoard>>> =
-		MineGameView::new_lazy(16, 16, 40).unwrap();
+	let view: GameView = MineGameView::new_lazy(16, 16, 40).unwrap();
 
-	cursive.add_layer(view);
+	cursive.add_layer(view.with_name(GAME_VIEW));
 
 	cursive.add_global_callback('q', Cursive::quit);
 
-	cursive.set_fps(2);
+	let mut runner = cursive.runner();
+
+	runner.refresh();
+
+	// only redraw continuously while the view asks for it, and stay event driven otherwise
+	while runner.is_running() {
+		runner.step();
+
+		let fps = runner
+			.call_on_name(GAME_VIEW, |v: &mut GameView| v.desired_fps())
+			.unwrap_or(0);
 
-	cursive.run();
+		runner.set_fps(fps);
+	}
 }
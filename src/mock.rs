@@ -0,0 +1,223 @@
+//! a scripted game board for testing and benchmarks
+//!
+//! This module exports the [`MockGameBoard`], a [`BaseGameBoard`] backed by a hand specified [`FlatBoard`] of [`VisibleTile`]'s with no hidden layout.
+//! It allows testing the ui and [`BaseGameBoard::do_event`] dispatch without generating a real board.
+
+use std::collections::VecDeque;
+
+use crate::gameboard::{
+	BaseGameBoard, FlatBoard, GameBoard, GameBoardEvent, GameState, IterBacking, NewBoardError,
+	UndoError, UnopenableError, VisibleTile,
+};
+
+/// a call made to a [`MockGameBoard`] that changes the board
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MockCall {
+	OpenTile(u16, u16),
+	OpenAround(u16, u16),
+	FlagTile(u16, u16),
+}
+
+pub struct MockGameBoard {
+	bombs: u32,
+	lost: bool,
+	// board is indexed as y/x but the api uses x/y
+	board: FlatBoard<VisibleTile>,
+	responses: VecDeque<Result<GameBoardEvent, UnopenableError>>,
+	calls: Vec<MockCall>,
+}
+
+impl MockGameBoard {
+	/// creates a mock board that renders exactly as board, which is indexed as y/x
+	#[must_use]
+	pub const fn new(board: FlatBoard<VisibleTile>, bombs: u32) -> Self {
+		Self {
+			bombs,
+			lost: false,
+			board,
+			responses: VecDeque::new(),
+			calls: Vec::new(),
+		}
+	}
+
+	/// queues the result of the next `open_tile` or `open_around` call, calls with no queued result return an empty [`GameBoardEvent::OpenCell`]
+	///
+	/// the mock does not change its tiles on open, use [`MockGameBoard::set_tile`] to script the effect
+	pub fn push_response(&mut self, res: Result<GameBoardEvent, UnopenableError>) {
+		self.responses.push_back(res);
+	}
+
+	/// overwrites the tile rendered at x/y, panicking on out of bounds
+	pub fn set_tile(&mut self, x: u16, y: u16, tile: VisibleTile) {
+		self.board[y.into()][usize::from(x)] = tile;
+	}
+
+	/// returns every call made to open or flag a tile, in order
+	#[must_use]
+	pub fn calls(&self) -> &[MockCall] {
+		&self.calls
+	}
+
+	fn count(&self, f: impl Fn(&VisibleTile) -> bool) -> u32 {
+		u32::try_from(self.board.iter_backing().filter(|t| f(t)).count()).unwrap()
+	}
+
+	fn scripted(&mut self, call: MockCall) -> Result<GameBoardEvent, UnopenableError> {
+		self.calls.push(call);
+
+		self.responses
+			.pop_front()
+			.unwrap_or_else(|| Ok(Vec::new().into()))
+	}
+}
+
+impl BaseGameBoard for MockGameBoard {
+	fn dimensions(&self) -> (u16, u16) {
+		(
+			self.board.dimensions().1.try_into().unwrap(),
+			self.board.len().try_into().unwrap(),
+		)
+	}
+
+	fn bomb_count(&self) -> u32 {
+		self.bombs
+	}
+
+	fn opened(&self) -> u32 {
+		self.count(|t| matches!(t, VisibleTile::Visible(_)))
+	}
+
+	fn flagged(&self) -> u32 {
+		self.count(|t| matches!(t, VisibleTile::Flagged | VisibleTile::WrongFlag))
+	}
+
+	/// creates a mock board where every tile is not visible, ignoring the clearing
+	fn with_clearing(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clear_x: u16,
+		clear_y: u16,
+	) -> Result<Self, NewBoardError> {
+		GameBoard::validate_board(x, y, bombs, (clear_x, clear_y))?;
+
+		Ok(Self::new(
+			FlatBoard::new(y.into(), x.into(), VisibleTile::NotVisible),
+			bombs,
+		))
+	}
+
+	fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		if !self.in_bounds(x, y) {
+			return Err(UnopenableError::OutOfBounds);
+		}
+
+		self.scripted(MockCall::OpenTile(x, y))
+	}
+
+	fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		if !self.in_bounds(x, y) {
+			return Err(UnopenableError::OutOfBounds);
+		}
+
+		self.scripted(MockCall::OpenAround(x, y))
+	}
+
+	/// toggles the flag of a tile like a real board would
+	fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+		let tile = self
+			.get_board_tile(x, y)
+			.ok_or(UnopenableError::OutOfBounds)?;
+
+		self.calls.push(MockCall::FlagTile(x, y));
+
+		match tile {
+			VisibleTile::NotVisible => self.set_tile(x, y, VisibleTile::Flagged),
+			VisibleTile::Flagged => self.set_tile(x, y, VisibleTile::NotVisible),
+			_ => return Err(UnopenableError::AlreadyOpen),
+		}
+
+		Ok(GameBoardEvent::flag_tile(x, y))
+	}
+
+	fn undo_move(&mut self, _: &GameBoardEvent) -> Result<(), UndoError> {
+		Ok(())
+	}
+
+	fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
+		self.board.get(y.into())?.get(usize::from(x)).copied()
+	}
+
+	fn lose_game(&mut self) {
+		self.lost = true;
+	}
+
+	fn win_game(&mut self) -> Result<(), u32> {
+		match self.tiles_left() {
+			0 => Ok(()),
+			left => Err(left),
+		}
+	}
+
+	fn state(&self) -> GameState {
+		if self.lost {
+			GameState::Lost
+		} else if self.tiles_left() == 0 {
+			GameState::Won
+		} else {
+			GameState::Playing
+		}
+	}
+
+	fn render(&self) -> FlatBoard<VisibleTile> {
+		self.board.clone()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::gameboard::{KeyEvent, Tile};
+
+	fn mock() -> MockGameBoard {
+		let mut mock = MockGameBoard::new(FlatBoard::new(2, 2, VisibleTile::NotVisible), 1);
+		mock.set_tile(1, 1, VisibleTile::Visible(Tile::One));
+
+		mock
+	}
+
+	#[test]
+	fn do_event_dispatches_by_visible_tile() {
+		let mut mock = mock();
+
+		mock.do_event(KeyEvent::Mouse1(0, 0)).unwrap();
+		mock.do_event(KeyEvent::Mouse1(1, 1)).unwrap();
+		mock.do_event(KeyEvent::Mouse2(1, 0)).unwrap();
+		mock.do_event(KeyEvent::Idle).unwrap();
+
+		assert_eq!(
+			mock.calls(),
+			[
+				MockCall::OpenTile(0, 0),
+				MockCall::OpenAround(1, 1),
+				MockCall::FlagTile(1, 0)
+			]
+		);
+		assert_eq!(mock.get_board_tile(1, 0), Some(VisibleTile::Flagged));
+	}
+
+	#[test]
+	fn scripted_responses_are_returned_in_order() {
+		let mut mock = mock();
+		mock.push_response(Err(UnopenableError::BombHit(0, 0)));
+
+		assert!(matches!(
+			mock.do_event(KeyEvent::Mouse1(0, 0)),
+			Err(UnopenableError::BombHit(0, 0))
+		));
+		assert_eq!(mock.open_tile(0, 1).unwrap(), Vec::new().into());
+
+		// the mock renders exactly the tiles it was given
+		assert_eq!(mock.render()[1][1], VisibleTile::Visible(Tile::One));
+	}
+}
@@ -0,0 +1,153 @@
+//! replay verification
+//!
+//! This module exports [`replay_to_board`], which regenerates a seeded [`GameBoard`] and plays a recorded stream of [`KeyEvent`]'s on it,
+//! so a submitted replay can be checked against the final state it claims.
+//! A [`ReplayBundle`] holds everything needed for that in one value, and is played back with [`replay_bundle_to_board`].
+
+use thiserror::Error;
+
+use crate::gameboard::{BaseGameBoard, GameBoard, KeyEvent, NewBoardError, UnopenableError};
+
+/// an error returned when a replay could not be played back
+#[derive(Error, Debug)]
+pub enum ReplayError {
+	#[error("the replay does not start with a mouse 1 press")]
+	NoOpening,
+	#[error("the board could not be generated: {0}")]
+	Board(#[from] NewBoardError),
+	#[error("event {index} of the replay failed: {source}")]
+	Event {
+		/// the index of the failed event in the replay
+		index: usize,
+		source: UnopenableError,
+	},
+	#[error("replay bundle version {0} is not supported")]
+	UnsupportedVersion(u16),
+	#[error("the replay was not played on a seeded board, so the board can not be regenerated")]
+	Unseeded,
+}
+
+/// the [`ReplayBundle::version`] written and read by this build
+pub const REPLAY_BUNDLE_VERSION: u16 = 1;
+
+/// a single logged input of a [`ReplayBundle`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SerializableFrame {
+	/// the time since the game started in microseconds
+	pub time_offset_micros: u64,
+	pub event: KeyEvent,
+}
+
+/// everything needed to regenerate and replay a logged game, made by [`LoggedGameBoard::to_replay_bundle`][crate::logged::LoggedGameBoard::to_replay_bundle]
+///
+/// with the `serde` feature it can be serialized to any serde format, the [`ReplayBundle::version`] is checked on playback rather than on deserialization
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ReplayBundle {
+	/// the format version, bumped whenever the meaning of a field changes
+	pub version: u16,
+	/// the x/y dimensions of the board
+	pub dimensions: (u16, u16),
+	pub bombs: u32,
+	/// the seed the board was generated from, replays of unseeded boards can be stored but not played back
+	pub seed: Option<u64>,
+	/// the x/y the board was generated to be clear around
+	pub clearing: Option<(u16, u16)>,
+	/// every logged input in order, starting with the opening move
+	pub frames: Vec<SerializableFrame>,
+}
+
+/// regenerates the board a replay was played on and applies every event of it in order
+///
+/// the first event must be the opening mouse 1 press, and the board is generated with [`GameBoard::with_clearing_seeded`] around it like a lazy board would.
+/// Hitting a bomb loses the game like it does during play, so only events after the end of a game fail
+pub fn replay_to_board(
+	x: u16,
+	y: u16,
+	bombs: u32,
+	seed: u64,
+	events: &[KeyEvent],
+) -> Result<GameBoard, ReplayError> {
+	let Some(&KeyEvent::Mouse1(open_x, open_y)) = events.first() else {
+		return Err(ReplayError::NoOpening);
+	};
+
+	let board = GameBoard::with_clearing_seeded(x, y, bombs, open_x, open_y, seed)?;
+
+	play(board, events)
+}
+
+/// regenerates the board of a bundle and applies every frame of it in order like [`replay_to_board`]
+///
+/// the board is generated around the stored clearing, or around the opening move if the bundle has none
+pub fn replay_bundle_to_board(bundle: &ReplayBundle) -> Result<GameBoard, ReplayError> {
+	if bundle.version != REPLAY_BUNDLE_VERSION {
+		return Err(ReplayError::UnsupportedVersion(bundle.version));
+	}
+
+	let seed = bundle.seed.ok_or(ReplayError::Unseeded)?;
+	let (x, y) = bundle.dimensions;
+
+	let events: Vec<KeyEvent> = bundle.frames.iter().map(|f| f.event).collect();
+
+	match bundle.clearing {
+		Some((clearx, cleary)) => play(
+			GameBoard::with_clearing_seeded(x, y, bundle.bombs, clearx, cleary, seed)?,
+			&events,
+		),
+		None => replay_to_board(x, y, bundle.bombs, seed, &events),
+	}
+}
+
+/// applies every event to a board in order, losing the game on a bomb hit
+fn play(mut board: GameBoard, events: &[KeyEvent]) -> Result<GameBoard, ReplayError> {
+	for (index, &event) in events.iter().enumerate() {
+		match board.do_event(event) {
+			Ok(()) => (),
+			Err(UnopenableError::BombHit(..)) => board.lose_game(),
+			Err(source) => return Err(ReplayError::Event { index, source }),
+		}
+	}
+
+	Ok(board)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn replay_matches_playing_the_seeded_board() {
+		let events = [KeyEvent::Mouse1(4, 4), KeyEvent::Mouse2(0, 0)];
+
+		let mut played = GameBoard::with_clearing_seeded(9, 9, 10, 4, 4, 11).unwrap();
+		for &event in &events {
+			played.do_event(event).unwrap();
+		}
+
+		let replayed = replay_to_board(9, 9, 10, 11, &events).unwrap();
+		assert_eq!(replayed.render(), played.render());
+	}
+
+	#[test]
+	fn replay_errors_name_the_failed_event() {
+		assert!(matches!(
+			replay_to_board(9, 9, 10, 11, &[]),
+			Err(ReplayError::NoOpening)
+		));
+		assert!(matches!(
+			replay_to_board(9, 9, 10, 11, &[KeyEvent::Mouse2(4, 4)]),
+			Err(ReplayError::NoOpening)
+		));
+
+		let events = [KeyEvent::Mouse1(4, 4), KeyEvent::Mouse1(9, 0)];
+		assert!(matches!(
+			replay_to_board(9, 9, 10, 11, &events),
+			Err(ReplayError::Event {
+				index: 1,
+				source: UnopenableError::OutOfBounds
+			})
+		));
+	}
+}
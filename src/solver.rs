@@ -0,0 +1,931 @@
+//! a deterministic minesweeper solver
+//!
+//! This module exports the [Solver], which deduces forced moves from the visible state of a [`BaseGameBoard`],
+//! and [autosolve], which plays a board to completion using those deductions and guessing only when no deduction is left.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rand::prelude::*;
+
+use crate::gameboard::{
+	narrow_xy, tiles_around, BaseGameBoard, FlatBoard, GameBoardEvent, GameState, UnopenableError,
+	VisibleTile,
+};
+
+/// a move the solver has proven to be correct
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum SolverAction {
+	/// the tile at x/y is guaranteed not to be a bomb
+	Open(u16, u16),
+	/// the tile at x/y is guaranteed to be a bomb
+	Flag(u16, u16),
+}
+
+/// a forced move and the opened numbers that prove it, returned by [`Solver::explain_step`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Deduction {
+	/// the proven move
+	pub action: SolverAction,
+	/// x/y of every number the deduction needs, one for a single number and two for a subset pair
+	pub because: Vec<(u16, u16)>,
+}
+
+/// the default cap of [`Solver::mine_probabilities`], around a million enumeration steps per component
+pub const DEFAULT_MAX_ENUMERATION: usize = 1 << 20;
+
+/// bomb probabilities of the frontier, returned by [`Solver::mine_probabilities`]
+#[derive(Clone, Debug)]
+pub struct MineProbabilities {
+	/// the chance in the range \[0,1\] of a bomb under every unknown tile bordering an opened number, keyed by x/y
+	pub probabilities: BTreeMap<(u16, u16), f64>,
+	/// true if no component took more than the enumeration cap, false if any was estimated because of it
+	pub exact: bool,
+	/// true if the numbers of some component can not all be satisfied, which means a flag around them is wrong.
+	/// The tiles of such components are estimated, which does not clear [`MineProbabilities::exact`]
+	pub contradiction: bool,
+}
+
+/// an opened number on the frontier and the unknown tiles it constrains
+#[derive(Clone, Debug)]
+pub struct Constraint {
+	/// x/y of the opened number
+	pub origin: (u16, u16),
+	/// sorted x/y coordinates of every unopened and unflagged tile around origin
+	pub unknowns: Vec<(u16, u16)>,
+	/// count of bombs among unknowns, which is the number minus its flagged neighbors
+	pub bombs: u8,
+}
+
+/// a group of frontier numbers that shares no unknown with any other group, returned by [`Solver::constraint_components`]
+///
+/// the bombs of a component do not affect the bombs of any other, so every component can be solved on its own
+#[derive(Clone, Debug)]
+pub struct ConstraintComponent {
+	/// sorted x/y coordinates of every unknown tile of the component
+	pub cells: Vec<(u16, u16)>,
+	/// every number constraining cells, in row major order of their origin
+	pub constraints: Vec<Constraint>,
+}
+
+impl Constraint {
+	/// returns true if every unknown of self is also an unknown of other
+	fn is_subset_of(&self, other: &Self) -> bool {
+		self.unknowns
+			.iter()
+			.all(|c| other.unknowns.binary_search(c).is_ok())
+	}
+
+	/// finds forced actions from this constraint alone
+	fn single_point(&self, out: &mut impl Extend<SolverAction>) {
+		if self.bombs == 0 {
+			out.extend(self.unknowns.iter().map(|&(x, y)| SolverAction::Open(x, y)));
+		} else if usize::from(self.bombs) == self.unknowns.len() {
+			out.extend(self.unknowns.iter().map(|&(x, y)| SolverAction::Flag(x, y)));
+		}
+	}
+
+	/// finds forced actions like [`Constraint::single_point`], citing because for every action that was not already proven
+	fn explain(&self, because: &[(u16, u16)], out: &mut BTreeMap<SolverAction, Vec<(u16, u16)>>) {
+		let mut actions = Vec::new();
+		self.single_point(&mut actions);
+
+		// the first proof of an action is never longer than a later one within a step
+		for action in actions {
+			out.entry(action).or_insert_with(|| because.to_vec());
+		}
+	}
+}
+
+/// a snapshot of the visible state of a board, and the frontier constraints derived from it
+///
+/// the solver trusts every flag on the board to be correct
+pub struct Solver {
+	view: FlatBoard<VisibleTile>,
+	constraints: Vec<Constraint>,
+	/// the unknowns around every number with more flags around it than its count, which proves a flag wrong
+	overflagged: Vec<Vec<(u16, u16)>>,
+}
+
+impl Solver {
+	/// builds the frontier constraints of the current visible state of a board
+	pub fn new<B: BaseGameBoard>(board: &B) -> Self {
+		Self::from_view(board.render(), &BTreeSet::new())
+	}
+
+	/// builds the frontier constraints of a rendered board, where the covered tiles in `known_safe` are treated as neither bombs nor unknowns
+	fn from_view(view: FlatBoard<VisibleTile>, known_safe: &BTreeSet<(u16, u16)>) -> Self {
+		let (dim_y, dim_x) = view.dimensions();
+		let dimensions = narrow_xy(dim_x, dim_y);
+		let mut constraints = Vec::new();
+		let mut overflagged = Vec::new();
+
+		for (y, row) in view.iter().enumerate() {
+			for (x, tile) in row.iter().enumerate() {
+				let count = match tile {
+					VisibleTile::Visible(t) => match t.as_count() {
+						Some(c) => c,
+						None => continue,
+					},
+					_ => continue,
+				};
+
+				let origin = narrow_xy(x, y);
+
+				let mut unknowns = Vec::new();
+				let mut flagged = 0u8;
+
+				for (nx, ny) in tiles_around(origin.0, origin.1, dimensions) {
+					match view[usize::from(ny)][usize::from(nx)] {
+						VisibleTile::NotVisible if !known_safe.contains(&(nx, ny)) => {
+							unknowns.push((nx, ny));
+						}
+						VisibleTile::Flagged | VisibleTile::WrongFlag => flagged += 1,
+						// known safe tiles constrain nothing, like opened ones
+						VisibleTile::NotVisible
+						| VisibleTile::Visible(_)
+						| VisibleTile::DetonatedBomb => (),
+					}
+				}
+
+				// an overflagged number is unsolvable, it is only kept to report the contradiction
+				if flagged > count {
+					overflagged.push(unknowns);
+					continue;
+				}
+
+				// a number with no unknowns left constrains nothing
+				if unknowns.is_empty() {
+					continue;
+				}
+
+				unknowns.sort_unstable();
+
+				constraints.push(Constraint {
+					origin,
+					unknowns,
+					bombs: count - flagged,
+				});
+			}
+		}
+
+		Self {
+			view,
+			constraints,
+			overflagged,
+		}
+	}
+
+	/// deduces moves from each number on its own, when a number is either fully flagged or has exactly as many unknowns as bombs left
+	#[must_use]
+	pub fn single_point_step(&self) -> Vec<SolverAction> {
+		let mut out = BTreeSet::new();
+
+		for c in &self.constraints {
+			c.single_point(&mut out);
+		}
+
+		out.into_iter().collect()
+	}
+
+	/// deduces moves from pairs of numbers where the unknowns of one number are a subset of the unknowns of another
+	///
+	/// the tiles in the difference of the two sets must then contain exactly the difference of their bomb counts,
+	/// which solves the 1-2-1 and 1-2-2 patterns that [`Solver::single_point_step`] cannot
+	#[must_use]
+	pub fn subset_step(&self) -> Vec<SolverAction> {
+		let mut out = BTreeSet::new();
+
+		self.for_each_subset(|_, _, difference| difference.single_point(&mut out));
+
+		out.into_iter().collect()
+	}
+
+	/// calls f with every pair of constraints where the unknowns of the first are a strict subset of the unknowns of the second,
+	/// and the constraint on the tiles that only the second has
+	fn for_each_subset(&self, mut f: impl FnMut(&Constraint, &Constraint, Constraint)) {
+		for a in &self.constraints {
+			for b in &self.constraints {
+				// two numbers more than 2 tiles apart can not share an unknown
+				if a.origin == b.origin
+					|| a.origin.0.abs_diff(b.origin.0) > 2
+					|| a.origin.1.abs_diff(b.origin.1) > 2
+					|| a.unknowns.len() >= b.unknowns.len()
+					|| !a.is_subset_of(b)
+				{
+					continue;
+				}
+
+				let difference = Constraint {
+					origin: b.origin,
+					unknowns: b
+						.unknowns
+						.iter()
+						.filter(|c| a.unknowns.binary_search(c).is_err())
+						.copied()
+						.collect(),
+					// an inconsistent board leaves no forced moves rather than wrong ones
+					bombs: match b.bombs.checked_sub(a.bombs) {
+						Some(v) => v,
+						None => continue,
+					},
+				};
+
+				f(a, b, difference);
+			}
+		}
+	}
+
+	/// returns the same moves as [`Solver::deduce`], each with the numbers that prove it
+	///
+	/// a move proven by several numbers cites the fewest it needs, a single number when possible
+	#[must_use]
+	pub fn explain_step(&self) -> Vec<Deduction> {
+		let mut out = BTreeMap::new();
+
+		for c in &self.constraints {
+			c.explain(&[c.origin], &mut out);
+		}
+
+		if out.is_empty() {
+			self.for_each_subset(|a, b, difference| {
+				difference.explain(&[a.origin, b.origin], &mut out);
+			});
+		}
+
+		out.into_iter()
+			.map(|(action, because)| Deduction { action, because })
+			.collect()
+	}
+
+	/// returns every group of unknown tiles that holds exactly one bomb where no number, current or future, can tell which tile it is
+	///
+	/// a group is the unknowns of a number with one bomb left, where every number touching the group touches all of it,
+	/// and no tile of the group borders an unknown tile outside the group that could later be opened for more information.
+	/// The total bomb count is not taken into account, so an endgame count can still resolve a reported group
+	#[must_use]
+	pub fn fifty_fifty_groups(&self) -> Vec<Vec<(u16, u16)>> {
+		let (dim_y, dim_x) = self.view.dimensions();
+		let dimensions = narrow_xy(dim_x, dim_y);
+
+		let mut groups = BTreeSet::new();
+
+		for c in &self.constraints {
+			if c.bombs != 1 || c.unknowns.len() < 2 {
+				continue;
+			}
+
+			let group = &c.unknowns;
+
+			let symmetric = self.constraints.iter().all(|other| {
+				let touches = other
+					.unknowns
+					.iter()
+					.any(|u| group.binary_search(u).is_ok());
+
+				!touches || c.is_subset_of(other)
+			});
+
+			let isolated = group.iter().all(|&(x, y)| {
+				tiles_around(x, y, dimensions).all(|(nx, ny)| {
+					self.view[usize::from(ny)][usize::from(nx)] != VisibleTile::NotVisible
+						|| group.binary_search(&(nx, ny)).is_ok()
+				})
+			});
+
+			if symmetric && isolated {
+				groups.insert(group.clone());
+			}
+		}
+
+		groups.into_iter().collect()
+	}
+
+	/// returns the chance of a bomb under every unknown tile bordering an opened number
+	///
+	/// the frontier is split into components of numbers that share unknowns, and each component is solved on its own
+	/// by counting the bomb placements that satisfy all of its numbers, weighing every placement equally.
+	/// A component that takes more than `max_enumeration` steps is instead estimated from the bomb density each number demands of its unknowns,
+	/// which is fast but can be far off for tiles whose numbers interact, and clears [`MineProbabilities::exact`].
+	/// A component with no placement that satisfies its numbers, or bordering a number with more flags than its count, is estimated the same way and sets [`MineProbabilities::contradiction`] instead.
+	/// Neither way takes the total bomb count into account, so placements with more or fewer bombs are not weighed by how likely the rest of the board makes them
+	#[must_use]
+	pub fn mine_probabilities(&self, max_enumeration: usize) -> MineProbabilities {
+		let mut probabilities = BTreeMap::new();
+		let mut exact = true;
+		// an overflagged number contradicts the board even if it borders no component
+		let mut contradiction = !self.overflagged.is_empty();
+
+		for component in self.constraint_components() {
+			let overflagged = self
+				.overflagged
+				.iter()
+				.flatten()
+				.any(|cell| component.cells.binary_search(cell).is_ok());
+
+			let enumerated = if overflagged {
+				Err(Unenumerable::Contradiction)
+			} else {
+				enumerate(&component, max_enumeration)
+			};
+
+			let chances = match enumerated {
+				Ok(chances) => chances,
+				Err(Unenumerable::OverBudget) => {
+					exact = false;
+					estimate(&component)
+				}
+				Err(Unenumerable::Contradiction) => {
+					contradiction = true;
+					estimate(&component)
+				}
+			};
+
+			probabilities.extend(component.cells.into_iter().zip(chances));
+		}
+
+		MineProbabilities {
+			probabilities,
+			exact,
+			contradiction,
+		}
+	}
+
+	/// partitions the frontier into components where no two components share an unknown, in row major order of their first number
+	///
+	/// two numbers are in the same component if they share an unknown, directly or through a chain of other numbers
+	#[must_use]
+	pub fn constraint_components(&self) -> Vec<ConstraintComponent> {
+		let mut parents: Vec<usize> = (0..self.constraints.len()).collect();
+
+		let mut owners = BTreeMap::new();
+
+		for (i, c) in self.constraints.iter().enumerate() {
+			for &cell in &c.unknowns {
+				let owner = *owners.entry(cell).or_insert(i);
+
+				let (a, b) = (root(&mut parents, owner), root(&mut parents, i));
+				parents[a] = b;
+			}
+		}
+
+		// keyed by the first constraint of each component, which keeps components in the order of their first number
+		let mut roots = BTreeMap::new();
+		let mut components: Vec<ConstraintComponent> = Vec::new();
+
+		for (i, c) in self.constraints.iter().enumerate() {
+			let next = components.len();
+			let idx = *roots.entry(root(&mut parents, i)).or_insert(next);
+
+			if idx == next {
+				components.push(ConstraintComponent {
+					cells: Vec::new(),
+					constraints: Vec::new(),
+				});
+			}
+
+			let component = &mut components[idx];
+			component.cells.extend_from_slice(&c.unknowns);
+			component.constraints.push(c.clone());
+		}
+
+		for component in &mut components {
+			component.cells.sort_unstable();
+			component.cells.dedup();
+		}
+
+		components
+	}
+
+	/// returns every unopened and unflagged tile of the snapshot
+	fn unknowns(&self) -> Vec<(u16, u16)> {
+		let mut out = Vec::new();
+
+		for (y, row) in self.view.iter().enumerate() {
+			for (x, tile) in row.iter().enumerate() {
+				if *tile == VisibleTile::NotVisible {
+					out.push(narrow_xy(x, y));
+				}
+			}
+		}
+
+		out
+	}
+
+	/// returns the next batch of forced moves, trying [`Solver::single_point_step`] before [`Solver::subset_step`]
+	#[must_use]
+	pub fn deduce(&self) -> Vec<SolverAction> {
+		let actions = self.single_point_step();
+
+		if actions.is_empty() {
+			self.subset_step()
+		} else {
+			actions
+		}
+	}
+}
+
+/// returns the root of i in a union find forest, halving the path to it
+fn root(parents: &mut [usize], mut i: usize) -> usize {
+	while parents[i] != i {
+		parents[i] = parents[parents[i]];
+		i = parents[i];
+	}
+
+	i
+}
+
+/// why [`enumerate`] could not count the placements of a component
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Unenumerable {
+	/// more than the budget of steps were taken
+	OverBudget,
+	/// no placement satisfies every constraint
+	Contradiction,
+}
+
+/// counts every bomb placement on the cells of component that satisfies its constraints, and returns the fraction of placements with a bomb on each cell
+fn enumerate(component: &ConstraintComponent, budget: usize) -> Result<Vec<f64>, Unenumerable> {
+	let ConstraintComponent { cells, constraints } = component;
+
+	// the local indices of the constraints on every cell
+	let mut cell_constraints = vec![Vec::new(); cells.len()];
+
+	for (k, c) in constraints.iter().enumerate() {
+		for u in &c.unknowns {
+			cell_constraints[cells.binary_search(u).unwrap()].push(k);
+		}
+	}
+
+	// bombs placed and cells left unassigned around each constraint
+	let mut placed = vec![0u8; constraints.len()];
+	let mut left: Vec<usize> = constraints.iter().map(|c| c.unknowns.len()).collect();
+
+	// assigns or unassigns cell i, returning whether every constraint on it can still be satisfied
+	let mut set = |i: usize, bomb: bool, assign: bool| {
+		cell_constraints[i].iter().fold(true, |feasible, &k| {
+			if assign {
+				left[k] -= 1;
+				placed[k] += u8::from(bomb);
+			} else {
+				left[k] += 1;
+				placed[k] -= u8::from(bomb);
+			}
+
+			let bombs = usize::from(constraints[k].bombs);
+			let placed = usize::from(placed[k]);
+
+			feasible && placed <= bombs && placed + left[k] >= bombs
+		})
+	};
+
+	let mut values: Vec<bool> = Vec::with_capacity(cells.len());
+	// placements are counted in floats, which are exact far beyond any budget
+	let mut bombs_at = vec![0.0; cells.len()];
+	let mut solutions = 0.0;
+	let mut steps = 0usize;
+	let mut next = false;
+
+	loop {
+		steps += 1;
+
+		if steps > budget {
+			return Err(Unenumerable::OverBudget);
+		}
+
+		let i = values.len();
+
+		if set(i, next, true) {
+			values.push(next);
+
+			if values.len() < cells.len() {
+				next = false;
+				continue;
+			}
+
+			solutions += 1.0;
+			steps += cells.len();
+
+			for (count, _) in bombs_at.iter_mut().zip(&values).filter(|(_, &v)| v) {
+				*count += 1.0;
+			}
+		} else {
+			set(i, next, false);
+
+			if !next {
+				next = true;
+				continue;
+			}
+		}
+
+		// backtrack to the last cell that has not tried being a bomb yet
+		loop {
+			let Some(bomb) = values.pop() else {
+				return if solutions > 0.0 {
+					Ok(bombs_at.iter().map(|&count| count / solutions).collect())
+				} else {
+					Err(Unenumerable::Contradiction)
+				};
+			};
+
+			set(values.len(), bomb, false);
+
+			if !bomb {
+				next = true;
+				break;
+			}
+		}
+	}
+}
+
+/// estimates the bomb chance of each cell as the mean density its constraints demand, or exactly 0 or 1 if any constraint forces it
+fn estimate(component: &ConstraintComponent) -> Vec<f64> {
+	let ConstraintComponent { cells, constraints } = component;
+
+	// the sum and count of densities on every cell, and whether a constraint forces it safe or a bomb
+	let mut densities = vec![(0.0, 0.0, None); cells.len()];
+
+	for c in constraints {
+		let density = f64::from(c.bombs) / f64::from(u32::try_from(c.unknowns.len()).unwrap());
+
+		for u in &c.unknowns {
+			let (sum, count, forced) = &mut densities[cells.binary_search(u).unwrap()];
+
+			*sum += density;
+			*count += 1.0;
+
+			if c.bombs == 0 {
+				*forced = Some(0.0);
+			} else if density >= 1.0 {
+				forced.get_or_insert(1.0);
+			}
+		}
+	}
+
+	densities
+		.into_iter()
+		.map(|(sum, count, forced)| forced.unwrap_or(sum / count))
+		.collect()
+}
+
+/// returns every covered tile that is provably safe to open from the current visible state of a board, without opening any
+///
+/// deductions are repeated until nothing new is found, using the flags and safe tiles found by earlier ones,
+/// so this finds more than a single [`Solver::deduce`] but never includes a tile that needs a guess
+pub fn all_safe_tiles<B: BaseGameBoard>(board: &B) -> Vec<(u16, u16)> {
+	let mut view = board.render();
+	let mut safe = BTreeSet::new();
+
+	loop {
+		let mut progressed = false;
+
+		for action in Solver::from_view(view.clone(), &safe).deduce() {
+			match action {
+				SolverAction::Open(x, y) => progressed |= safe.insert((x, y)),
+				SolverAction::Flag(x, y) => {
+					let tile = &mut view[usize::from(y)][usize::from(x)];
+
+					progressed |= *tile != VisibleTile::Flagged;
+					*tile = VisibleTile::Flagged;
+				}
+			}
+		}
+
+		if !progressed {
+			return safe.into_iter().collect();
+		}
+	}
+}
+
+/// applies solver actions to a board in order, returning false if the game was lost or can not continue
+fn apply<B: BaseGameBoard>(board: &mut B, actions: Vec<SolverAction>) -> bool {
+	for action in actions {
+		let res = match action {
+			SolverAction::Open(x, y) => board.open_tile(x, y).map(drop),
+			SolverAction::Flag(x, y) => match board.get_board_tile(x, y) {
+				Some(VisibleTile::NotVisible) => board.flag_tile(x, y).map(drop),
+				_ => Ok(()),
+			},
+		};
+
+		match res {
+			// an earlier action in this batch cascaded over this tile
+			Ok(()) | Err(UnopenableError::AlreadyOpen) => (),
+			Err(UnopenableError::BombHit(..)) => {
+				board.lose_game();
+				return false;
+			}
+			Err(_) => return false,
+		}
+	}
+
+	true
+}
+
+/// the result of an [autosolve] run
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct AutosolveOutcome {
+	/// whether the board was won under its win condition
+	pub won: bool,
+	/// how many times the solver had no deduction left and had to open a random tile
+	pub guesses: u32,
+}
+
+/// plays a board until it is won or lost
+///
+/// each turn applies [`Solver::deduce`], and only if it found nothing opens a random unknown tile
+pub fn autosolve<B: BaseGameBoard>(board: &mut B, rng: &mut impl Rng) -> AutosolveOutcome {
+	let mut guesses = 0u32;
+
+	while board.state() == GameState::Playing {
+		let solver = Solver::new(board);
+
+		let mut actions = solver.deduce();
+
+		if actions.is_empty() {
+			let guess = match solver.unknowns().choose(rng) {
+				Some(&(x, y)) => SolverAction::Open(x, y),
+				// remaining tiles are all flagged, but the board was not won so a flag is wrong
+				None => break,
+			};
+
+			guesses += 1;
+			actions.push(guess);
+		}
+
+		if !apply(board, actions) {
+			break;
+		}
+	}
+
+	AutosolveOutcome {
+		won: board.state() == GameState::Won,
+		guesses,
+	}
+}
+
+/// applies a single move to a board, the first move of [`Solver::deduce`] or a random guess if it found nothing, and returns its event
+///
+/// this is [`autosolve`] one move at a time, for animating the solver.
+/// Returns [`None`] once the game is over or can not continue, including when the move hits a bomb and loses the game
+pub fn solver_step<B: BaseGameBoard>(board: &mut B, rng: &mut impl Rng) -> Option<GameBoardEvent> {
+	if board.state() != GameState::Playing {
+		return None;
+	}
+
+	let solver = Solver::new(board);
+
+	let action = if let Some(&action) = solver.deduce().first() {
+		action
+	} else {
+		let &(x, y) = solver.unknowns().choose(rng)?;
+		SolverAction::Open(x, y)
+	};
+
+	let res = match action {
+		SolverAction::Open(x, y) => board.open_tile(x, y),
+		SolverAction::Flag(x, y) => board.flag_tile(x, y),
+	};
+
+	match res {
+		Ok(event) => Some(event),
+		Err(UnopenableError::BombHit(..)) => {
+			board.lose_game();
+			None
+		}
+		Err(_) => None,
+	}
+}
+
+/// applies [`Solver::deduce`] until it finds nothing, and returns whether the board was won without a single guess
+pub fn solve_without_guessing<B: BaseGameBoard>(board: &mut B) -> bool {
+	while board.state() == GameState::Playing {
+		let actions = Solver::new(board).deduce();
+
+		if actions.is_empty() || !apply(board, actions) {
+			return false;
+		}
+	}
+
+	board.state() == GameState::Won
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::gameboard::GameBoard;
+
+	/// parses a layout in the format of [`GameBoard::from_board_format`] and opens x/y on it
+	fn opened(layout: &str, x: u16, y: u16) -> GameBoard {
+		let mut gb = GameBoard::from_board_format(layout).unwrap();
+		gb.open_tile(x, y).unwrap();
+
+		gb
+	}
+
+	#[test]
+	fn subset_solves_1_2_1() {
+		// the opened row reads 1 2 1 under three unknowns
+		let gb = opened("101\n000\n000", 1, 2);
+		let solver = Solver::new(&gb);
+
+		assert!(solver.single_point_step().is_empty());
+		assert_eq!(
+			solver.subset_step(),
+			[SolverAction::Flag(0, 0), SolverAction::Flag(2, 0)]
+		);
+	}
+
+	#[test]
+	fn subset_solves_1_2_2_1() {
+		// the opened row reads 1 2 2 1 under four unknowns, which are two 1-2-2 patterns from either side
+		let gb = opened("0110\n0000\n0000", 0, 2);
+		let solver = Solver::new(&gb);
+
+		assert!(solver.single_point_step().is_empty());
+		assert_eq!(
+			solver.subset_step(),
+			[SolverAction::Flag(1, 0), SolverAction::Flag(2, 0)]
+		);
+	}
+
+	#[test]
+	fn subset_lets_autosolve_finish_without_guessing() {
+		let mut gb = opened("101\n000\n000", 1, 2);
+
+		assert!(solve_without_guessing(&mut gb));
+		assert_eq!(gb.state(), GameState::Won);
+	}
+
+	#[test]
+	fn explain_step_cites_the_constraining_numbers() {
+		let mut gb = GameBoard::from_board_format("100\n000\n000").unwrap();
+		gb.options_mut().auto_cascade = false;
+		gb.open_tile(1, 1).unwrap();
+		gb.flag_tile(0, 0).unwrap();
+
+		// the flag satisfies the 1, so every other tile around it is safe because of it alone
+		let deductions = Solver::new(&gb).explain_step();
+		assert_eq!(deductions.len(), 7);
+		assert!(deductions
+			.iter()
+			.all(|d| matches!(d.action, SolverAction::Open(..)) && d.because == [(1, 1)]));
+
+		// the 1 2 1 row needs a pair of numbers for each bomb
+		let gb = opened("101\n000\n000", 1, 2);
+		let deductions = Solver::new(&gb).explain_step();
+		let flag_right = deductions
+			.iter()
+			.find(|d| d.action == SolverAction::Flag(2, 0))
+			.unwrap();
+		assert_eq!(flag_right.because.len(), 2);
+		assert!(flag_right.because.contains(&(1, 1)));
+	}
+
+	#[test]
+	fn all_safe_tiles_repeats_deductions_to_a_fixpoint() {
+		// the opened row reads 1 1 1 1 1 1, the middle two are safe at once and the edges only once the bombs next to them are known
+		let gb = opened("010010\n000000\n000000", 0, 2);
+
+		let first: Vec<_> = Solver::new(&gb)
+			.deduce()
+			.into_iter()
+			.filter_map(|action| match action {
+				SolverAction::Open(x, y) => Some((x, y)),
+				SolverAction::Flag(..) => None,
+			})
+			.collect();
+		assert_eq!(first, [(2, 0), (3, 0)]);
+
+		let before = gb.render();
+		assert_eq!(all_safe_tiles(&gb), [(0, 0), (2, 0), (3, 0), (5, 0)]);
+		assert_eq!(gb.render(), before);
+
+		// with five tiles the same row only proves the middle safe, the rest needs a guess
+		let gb = opened("10010\n00000\n00000", 0, 2);
+		assert_eq!(all_safe_tiles(&gb), [(2, 0)]);
+	}
+
+	#[test]
+	fn solver_step_plays_one_move_at_a_time_to_the_end() {
+		use rand::{rngs::StdRng, SeedableRng};
+
+		let mut rng = StdRng::seed_from_u64(5);
+
+		// the 1 2 1 row is solved by deductions alone, one move per step
+		let mut gb = opened("101\n000\n000", 1, 2);
+		assert_eq!(
+			solver_step(&mut gb, &mut rng),
+			Some(GameBoardEvent::flag_tile(0, 0))
+		);
+		assert_eq!(gb.flagged(), 1);
+
+		while solver_step(&mut gb, &mut rng).is_some() {}
+		assert_eq!(gb.state(), GameState::Won);
+		assert_eq!(solver_step(&mut gb, &mut rng), None);
+
+		// a seeded beginner board ends within one move per tile, guessing where it has to
+		let mut gb = GameBoard::with_clearing_seeded(9, 9, 10, 4, 4, 8).unwrap();
+		gb.open_tile(4, 4).unwrap();
+
+		let steps = std::iter::from_fn(|| solver_step(&mut gb, &mut rng))
+			.take(81)
+			.count();
+
+		assert!(steps > 0);
+		assert_ne!(gb.state(), GameState::Playing);
+	}
+
+	#[test]
+	fn fifty_fifty_groups_finds_only_unresolvable_pairs() {
+		// the opened 1 1 row under two unknowns can never tell which holds the bomb
+		let gb = opened("10\n00\n00", 0, 2);
+		assert_eq!(
+			Solver::new(&gb).fifty_fifty_groups(),
+			[vec![(0, 0), (1, 0)]]
+		);
+
+		// the pairs of the 1 2 1 row overlap other numbers, which solve them
+		let gb = opened("101\n000\n000", 1, 2);
+		assert!(Solver::new(&gb).fifty_fifty_groups().is_empty());
+	}
+
+	#[test]
+	fn mine_probabilities_are_capped_and_report_contradictions() {
+		// the 1 2 1 row is exact
+		let gb = opened("101\n000\n000", 1, 2);
+		let chances = Solver::new(&gb).mine_probabilities(DEFAULT_MAX_ENUMERATION);
+		assert!(chances.exact && !chances.contradiction);
+		assert!(chances.probabilities[&(1, 0)] < f64::EPSILON);
+		assert!(chances.probabilities[&(0, 0)] > 1.0 - f64::EPSILON);
+
+		// a single frontier along a 200 wide board, opened from the empty bottom row
+		let top: String = (0..200)
+			.map(|x| if x % 3 == 0 { '1' } else { '0' })
+			.collect();
+		let zeros = "0".repeat(200);
+		let gb = opened(&format!("{top}\n{zeros}\n{zeros}"), 0, 2);
+
+		let solver = Solver::new(&gb);
+		assert_eq!(solver.constraint_components().len(), 1);
+
+		let start = std::time::Instant::now();
+		let capped = solver.mine_probabilities(1000);
+		assert!(start.elapsed() < std::time::Duration::from_secs(1));
+
+		assert!(!capped.exact && !capped.contradiction);
+		assert_eq!(capped.probabilities.len(), 200);
+		assert!(capped
+			.probabilities
+			.values()
+			.all(|p| (0.0..=1.0).contains(p)));
+
+		// a wrong flag makes the 1 at 1/1 deny the bomb the 1 at 1/0 needs
+		let mut gb = GameBoard::from_board_format("100\n000\n000").unwrap();
+		gb.options_mut().auto_cascade = false;
+		gb.open_tile(1, 0).unwrap();
+		gb.open_tile(1, 1).unwrap();
+		gb.flag_tile(2, 2).unwrap();
+
+		let contradicted = Solver::new(&gb).mine_probabilities(DEFAULT_MAX_ENUMERATION);
+		assert!(contradicted.exact && contradicted.contradiction);
+		assert_eq!(contradicted.probabilities.len(), 6);
+
+		// two flags around a 1 are a contradiction even though the 1 constrains nothing
+		let mut gb = GameBoard::from_board_format("100\n000\n000").unwrap();
+		gb.options_mut().auto_cascade = false;
+		gb.open_tile(1, 0).unwrap();
+		gb.flag_tile(0, 0).unwrap();
+		gb.flag_tile(2, 0).unwrap();
+
+		let overflagged = Solver::new(&gb).mine_probabilities(DEFAULT_MAX_ENUMERATION);
+		assert!(overflagged.exact && overflagged.contradiction);
+
+		// a component sharing unknowns with an overflagged number is estimated as a contradiction
+		gb.open_tile(0, 2).unwrap();
+
+		let solver = Solver::new(&gb);
+		assert_eq!(solver.constraint_components().len(), 1);
+		let overflagged = solver.mine_probabilities(DEFAULT_MAX_ENUMERATION);
+		assert!(overflagged.exact && overflagged.contradiction);
+	}
+
+	#[test]
+	fn separated_frontiers_are_separate_components() {
+		// a column of zeros splits the numbers along the left bomb from those along the right one
+		let mut gb = GameBoard::from_board_format("10001\n00000\n00000").unwrap();
+		gb.open_tile(2, 2).unwrap();
+
+		let components = Solver::new(&gb).constraint_components();
+		assert_eq!(components.len(), 2);
+
+		let [left, right] = &components[..] else {
+			unreachable!()
+		};
+		assert_eq!(left.cells, [(0, 0)]);
+		assert_eq!(right.cells, [(4, 0)]);
+		assert!(left.constraints.iter().all(|c| c.origin.0 <= 1));
+		assert!(right.constraints.iter().all(|c| c.origin.0 >= 3));
+		assert_eq!(left.constraints.len(), 3);
+		assert_eq!(right.constraints.len(), 3);
+	}
+}
@@ -0,0 +1,391 @@
+//! a game board with u32 coordinates for very large sparse boards
+//!
+//! This module exports the [`SparseGameBoard`], which stores only the bombs, opened and flagged tiles instead of every tile,
+//! so boards far beyond the [`MAX_DIMENSION`][crate::gameboard::MAX_DIMENSION] of a [`GameBoard`][crate::gameboard::GameBoard] fit into memory as long as little of them is played.
+//!
+//! Memory grows with every bomb, opened and flagged tile, each taking an entry of a [`BTreeSet`] of around 10 bytes,
+//! and every tile lookup is a logarithmic search instead of an index, so a dense or fully played board is several times slower and larger than a [`GameBoard`][crate::gameboard::GameBoard].
+//! A cascade stores every tile it opens, and on a sparse board a single zero can cascade over most of the board,
+//! so research boards usually turn off [`SparseGameBoard::set_auto_cascade`].
+//!
+//! The [`BaseGameBoard`][crate::gameboard::BaseGameBoard] trait and the ui stay on u16 coordinates, [`SparseGameBoard::render_window`] renders a u16 sized view of any region for them
+
+use std::collections::BTreeSet;
+
+use rand::prelude::*;
+
+use crate::gameboard::{
+	FlatBoard, GameState, IterBackingMut, MineCount, NewBoardError, Tile, UnopenableError,
+	VisibleTile, MAX_BOMBS,
+};
+
+/// the result of [`SparseGameBoard::open_tile`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SparseOpen {
+	/// the tile was safe, every tile that was opened is listed in the order the cascade reached them
+	Opened(Vec<(u32, u32)>),
+	/// the tile was a bomb and the game is lost
+	Detonated,
+}
+
+#[derive(Clone, Debug)]
+pub struct SparseGameBoard {
+	dimensions: (u32, u32),
+	bombs: BTreeSet<(u32, u32)>,
+	opened: BTreeSet<(u32, u32)>,
+	flagged: BTreeSet<(u32, u32)>,
+	/// x/y of the bomb that was hit, the game is lost once this is set
+	detonated: Option<(u32, u32)>,
+	auto_cascade: bool,
+}
+
+/// yields every in bounds x/y coordinate in the 3x3 grid around a tile on a board of the given x/y dimensions, excluding the tile itself
+fn tiles_around(x: u32, y: u32, (dim_x, dim_y): (u32, u32)) -> impl Iterator<Item = (u32, u32)> {
+	(y.saturating_sub(1)..=y.saturating_add(1))
+		.flat_map(move |ny| (x.saturating_sub(1)..=x.saturating_add(1)).map(move |nx| (nx, ny)))
+		.filter(move |&(nx, ny)| nx < dim_x && ny < dim_y)
+		.filter(move |&coord| coord != (x, y))
+}
+
+// construction
+impl SparseGameBoard {
+	/// validates the dimensions and bomb count of a new board, where reserved tiles must stay free of bombs
+	fn validate(x: u32, y: u32, bombs: u64, reserved: u64) -> Result<(), NewBoardError> {
+		if x == 0 || y == 0 {
+			return Err(NewBoardError::ZeroDimension);
+		}
+
+		if bombs > u64::from(MAX_BOMBS) {
+			return Err(NewBoardError::SizeConstraintOverflow);
+		}
+
+		if u64::from(x) * u64::from(y) - reserved < bombs {
+			return Err(NewBoardError::BombOverflow);
+		}
+
+		Ok(())
+	}
+
+	const fn empty(x: u32, y: u32) -> Self {
+		Self {
+			dimensions: (x, y),
+			bombs: BTreeSet::new(),
+			opened: BTreeSet::new(),
+			flagged: BTreeSet::new(),
+			detonated: None,
+			auto_cascade: true,
+		}
+	}
+
+	/// generates a board with bombs at exactly the given x/y coordinates, which must be in bounds and unique
+	pub fn with_bombs(x: u32, y: u32, bombs: &[(u32, u32)]) -> Result<Self, NewBoardError> {
+		Self::validate(x, y, u64::try_from(bombs.len()).unwrap(), 0)?;
+
+		let mut gb = Self::empty(x, y);
+
+		for &(bx, by) in bombs {
+			if !(bx < x && by < y) {
+				return Err(NewBoardError::BombOutOfBounds);
+			}
+
+			if !gb.bombs.insert((bx, by)) {
+				return Err(NewBoardError::DuplicateBomb);
+			}
+		}
+
+		Ok(gb)
+	}
+
+	/// generates a board without bombs in the 3x3 zone around clearx/cleary, where the same seed and configuration always generate the same layout
+	///
+	/// bombs are placed by drawing random tiles until enough distinct ones were drawn,
+	/// which is fast for sparse layouts and gets slower as the density approaches 1
+	pub fn with_clearing_seeded(
+		x: u32,
+		y: u32,
+		bombs: u64,
+		clearx: u32,
+		cleary: u32,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		if !(clearx < x && cleary < y) {
+			return Err(NewBoardError::SizeConstraintOverflow);
+		}
+
+		let zone: BTreeSet<_> = tiles_around(clearx, cleary, (x, y))
+			.chain([(clearx, cleary)])
+			.collect();
+
+		Self::validate(x, y, bombs, u64::try_from(zone.len()).unwrap())?;
+
+		let mut gb = Self::empty(x, y);
+		let mut rng = StdRng::seed_from_u64(seed);
+
+		while u64::try_from(gb.bombs.len()).unwrap() < bombs {
+			let bomb = (rng.gen_range(0..x), rng.gen_range(0..y));
+
+			if !zone.contains(&bomb) {
+				gb.bombs.insert(bomb);
+			}
+		}
+
+		Ok(gb)
+	}
+
+	/// sets whether opening a zero also opens every tile around it, which is on by default
+	///
+	/// a cascade on a sparse board can reach most of the board and store every tile it opens
+	pub const fn set_auto_cascade(&mut self, auto_cascade: bool) {
+		self.auto_cascade = auto_cascade;
+	}
+}
+
+// state
+impl SparseGameBoard {
+	/// returns the dimensions of the board in x/y form
+	#[must_use]
+	pub const fn dimensions(&self) -> (u32, u32) {
+		self.dimensions
+	}
+
+	/// returns the computed x*y area of the board with no possibility of overflow
+	#[must_use]
+	pub const fn area(&self) -> u64 {
+		self.dimensions.0 as u64 * self.dimensions.1 as u64
+	}
+
+	/// returns the count of bombs in this board
+	#[must_use]
+	pub fn bomb_count(&self) -> u64 {
+		u64::try_from(self.bombs.len()).unwrap()
+	}
+
+	/// returns how many tiles have been opened
+	#[must_use]
+	pub fn opened(&self) -> u64 {
+		u64::try_from(self.opened.len()).unwrap()
+	}
+
+	/// returns how many tiles have been flagged
+	#[must_use]
+	pub fn flagged(&self) -> u64 {
+		u64::try_from(self.flagged.len()).unwrap()
+	}
+
+	/// returns the x/y of the bomb that ended the game, if it was lost
+	#[must_use]
+	pub const fn detonated(&self) -> Option<(u32, u32)> {
+		self.detonated
+	}
+
+	/// returns whether the game is still being played, was won by opening every non bomb tile, or was lost
+	#[must_use]
+	pub fn state(&self) -> GameState {
+		if self.detonated.is_some() {
+			GameState::Lost
+		} else if self.opened() + self.bomb_count() == self.area() {
+			GameState::Won
+		} else {
+			GameState::Playing
+		}
+	}
+
+	const fn in_bounds(&self, x: u32, y: u32) -> bool {
+		x < self.dimensions.0 && y < self.dimensions.1
+	}
+
+	/// counts the bombs around x/y
+	fn count_at(&self, x: u32, y: u32) -> MineCount {
+		tiles_around(x, y, self.dimensions)
+			.filter(|xy| self.bombs.contains(xy))
+			.fold(MineCount::ZERO, |count, _| count.saturating_add(1))
+	}
+
+	/// gets a tile on the board, or [`None`] if x/y is out of bounds
+	///
+	/// once the game is lost every unflagged bomb is visible and wrong flags are shown, like on a [`GameBoard`][crate::gameboard::GameBoard]
+	#[must_use]
+	pub fn get_board_tile(&self, x: u32, y: u32) -> Option<VisibleTile> {
+		if !self.in_bounds(x, y) {
+			return None;
+		}
+
+		let lost = self.detonated.is_some();
+		let bomb = self.bombs.contains(&(x, y));
+
+		Some(if self.detonated == Some((x, y)) {
+			VisibleTile::DetonatedBomb
+		} else if self.flagged.contains(&(x, y)) {
+			if lost && !bomb {
+				VisibleTile::WrongFlag
+			} else {
+				VisibleTile::Flagged
+			}
+		} else if lost && bomb {
+			VisibleTile::Visible(Tile::Bomb)
+		} else if self.opened.contains(&(x, y)) {
+			VisibleTile::Visible(self.count_at(x, y).into())
+		} else {
+			VisibleTile::NotVisible
+		})
+	}
+
+	/// returns a [`FlatBoard`] of the `w*h` region starting at x0/y0 rendered as y/x, where index 0/0 of the result is tile x0/y0
+	///
+	/// the region is clamped to the board bounds, so it may be smaller than `w*h` or empty
+	#[must_use]
+	pub fn render_window(&self, x0: u32, y0: u32, w: u16, h: u16) -> FlatBoard<VisibleTile> {
+		let (dim_x, dim_y) = self.dimensions;
+
+		let x1 = x0.saturating_add(w.into()).min(dim_x);
+		let y1 = y0.saturating_add(h.into()).min(dim_y);
+
+		// the window is at most w*h, so both sides fit into usize
+		let side = |a: u32, b: u32| usize::try_from(b.saturating_sub(a)).unwrap();
+
+		let mut board = FlatBoard::new(side(y0, y1), side(x0, x1), VisibleTile::NotVisible);
+
+		for (j, (x, y)) in board
+			.iter_backing_mut()
+			.zip((y0..y1).flat_map(|y| (x0..x1).map(move |x| (x, y))))
+		{
+			*j = self.get_board_tile(x, y).unwrap();
+		}
+
+		board
+	}
+}
+
+// play
+impl SparseGameBoard {
+	/// opens the given tile, cascading over zeros if auto cascade is on
+	///
+	/// opening a bomb loses the game and returns [`SparseOpen::Detonated`], every move after that returns [`UnopenableError::GameOver`]
+	pub fn open_tile(&mut self, x: u32, y: u32) -> Result<SparseOpen, UnopenableError> {
+		self.assert_playing()?;
+
+		if !self.in_bounds(x, y) {
+			return Err(UnopenableError::OutOfBounds);
+		}
+
+		if self.opened.contains(&(x, y)) {
+			return Err(UnopenableError::AlreadyOpen);
+		}
+
+		if self.flagged.contains(&(x, y)) {
+			return Err(UnopenableError::FlaggedTile);
+		}
+
+		if self.bombs.contains(&(x, y)) {
+			self.detonated = Some((x, y));
+			return Ok(SparseOpen::Detonated);
+		}
+
+		self.opened.insert((x, y));
+
+		let mut region = vec![(x, y)];
+		let mut next = 0;
+
+		// only zeros spread the cascade, and all tiles around a zero are not bombs
+		while let Some(&(x, y)) = region.get(next) {
+			next += 1;
+
+			if !self.auto_cascade || self.count_at(x, y) != MineCount::ZERO {
+				continue;
+			}
+
+			for (nx, ny) in tiles_around(x, y, self.dimensions) {
+				if !self.flagged.contains(&(nx, ny)) && self.opened.insert((nx, ny)) {
+					region.push((nx, ny));
+				}
+			}
+		}
+
+		Ok(SparseOpen::Opened(region))
+	}
+
+	/// flags or unflags a tile depending on whether it is flagged already, erroring on an already open tile
+	pub fn flag_tile(&mut self, x: u32, y: u32) -> Result<(), UnopenableError> {
+		self.assert_playing()?;
+
+		if !self.in_bounds(x, y) {
+			return Err(UnopenableError::OutOfBounds);
+		}
+
+		if self.opened.contains(&(x, y)) {
+			return Err(UnopenableError::AlreadyOpen);
+		}
+
+		if !self.flagged.remove(&(x, y)) {
+			self.flagged.insert((x, y));
+		}
+
+		Ok(())
+	}
+
+	fn assert_playing(&self) -> Result<(), UnopenableError> {
+		match self.state() {
+			GameState::Playing => Ok(()),
+			GameState::Won | GameState::Lost => Err(UnopenableError::GameOver),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	// wider than any u16 coordinate
+	const WIDE: u32 = 70_000;
+
+	#[test]
+	fn cascades_past_u16_coordinates() {
+		let mut gb = SparseGameBoard::with_bombs(WIDE, 2, &[(WIDE - 1, 0)]).unwrap();
+
+		let SparseOpen::Opened(opened) = gb.open_tile(0, 1).unwrap() else {
+			panic!("opened a bomb");
+		};
+
+		// the tile below the bomb only borders numbers, so the cascade does not reach it
+		assert_eq!(opened.len(), usize::try_from(gb.area()).unwrap() - 2);
+		assert_eq!(
+			gb.get_board_tile(WIDE - 2, 0),
+			Some(VisibleTile::Visible(Tile::One))
+		);
+
+		gb.open_tile(WIDE - 1, 1).unwrap();
+		assert_eq!(gb.state(), GameState::Won);
+	}
+
+	#[test]
+	fn detonation_ends_the_game() {
+		let mut gb = SparseGameBoard::with_bombs(WIDE, 3, &[(WIDE - 1, 2)]).unwrap();
+		gb.set_auto_cascade(false);
+
+		gb.flag_tile(0, 0).unwrap();
+		assert_eq!(gb.open_tile(WIDE - 1, 2).unwrap(), SparseOpen::Detonated);
+
+		assert_eq!(gb.state(), GameState::Lost);
+		assert_eq!(gb.detonated(), Some((WIDE - 1, 2)));
+		assert_eq!(gb.get_board_tile(0, 0), Some(VisibleTile::WrongFlag));
+		assert!(matches!(gb.open_tile(1, 1), Err(UnopenableError::GameOver)));
+	}
+
+	#[test]
+	fn seeded_generation_is_repeatable_and_clear() {
+		let a = SparseGameBoard::with_clearing_seeded(WIDE, WIDE, 1000, 5, 5, 7).unwrap();
+		let b = SparseGameBoard::with_clearing_seeded(WIDE, WIDE, 1000, 5, 5, 7).unwrap();
+
+		assert_eq!(a.bombs, b.bombs);
+		assert_eq!(a.bomb_count(), 1000);
+		assert!((4..=6).all(|y| (4..=6).all(|x| !a.bombs.contains(&(x, y)))));
+	}
+
+	#[test]
+	fn render_window_is_clamped() {
+		let gb = SparseGameBoard::with_bombs(WIDE, 2, &[]).unwrap();
+
+		assert_eq!(gb.render_window(WIDE - 3, 1, 10, 10).dimensions(), (1, 3));
+		assert_eq!(gb.render_window(WIDE, 0, 10, 10).dimensions(), (2, 0));
+	}
+}
@@ -0,0 +1,278 @@
+//! a thread safe shared game board
+//!
+//! This module exports the [`SyncGameBoard`], a cloneable handle to a [`BaseGameBoard`] behind an [`RwLock`],
+//! so that for example a solver thread can make moves while the ui thread renders the same board.
+//!
+//! Every method takes the lock for the duration of that one call only, reads take a shared lock and moves take an exclusive lock.
+//! No lock is ever held while calling back into user code, except for [`SyncGameBoard::with_board`],
+//! which must not use any handle to the same board inside its closure or it will deadlock.
+
+use std::sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use crate::gameboard::{
+	BaseGameBoard, BoardSnapshot, ChordPolicy, EventOutcome, FlatBoard, GameBoardEvent, GameState,
+	KeyEvent, MineCount, NewBoardError, RestoreError, TileCensus, UndoError, UnopenableError,
+	VisibleTile,
+};
+
+pub struct SyncGameBoard<B: BaseGameBoard>(Arc<RwLock<B>>);
+
+// manually implementing circumvents B being Clone, every clone is a handle to the same board
+impl<B: BaseGameBoard> Clone for SyncGameBoard<B> {
+	fn clone(&self) -> Self {
+		Self(Arc::clone(&self.0))
+	}
+}
+
+impl<B: BaseGameBoard> SyncGameBoard<B> {
+	pub fn new(board: B) -> Self {
+		Self(Arc::new(RwLock::new(board)))
+	}
+
+	/// runs f with exclusive access to the board, for several moves that must not interleave with other threads
+	///
+	/// using any handle to this board inside f deadlocks
+	pub fn with_board<R>(&self, f: impl FnOnce(&mut B) -> R) -> R {
+		f(&mut self.write())
+	}
+
+	// a panic while holding the lock may have left the board half updated, so poisoning is not recovered from
+	fn read(&self) -> RwLockReadGuard<'_, B> {
+		self.0.read().expect("board lock was poisoned")
+	}
+
+	fn write(&self) -> RwLockWriteGuard<'_, B> {
+		self.0.write().expect("board lock was poisoned")
+	}
+}
+
+macro_rules! impl_read {
+	($fn_name:ident, $restype:ty) => {
+		fn $fn_name(&self) -> $restype {
+			self.read().$fn_name()
+		}
+	};
+}
+
+// reads of several tiles around x/y, which must all see the same board
+macro_rules! impl_read_at {
+	($fn_name:ident, $restype:ty) => {
+		fn $fn_name(&self, x: u16, y: u16) -> $restype {
+			self.read().$fn_name(x, y)
+		}
+	};
+}
+
+macro_rules! impl_move {
+	($fn_name:ident) => {
+		fn $fn_name(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+			self.write().$fn_name(x, y)
+		}
+	};
+}
+
+impl<B: BaseGameBoard> BaseGameBoard for SyncGameBoard<B> {
+	fn with_clearing(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+	) -> Result<Self, NewBoardError> {
+		B::with_clearing(x, y, bombs, clearx, cleary).map(Self::new)
+	}
+
+	fn with_clearing_seeded(
+		x: u16,
+		y: u16,
+		bombs: u32,
+		clearx: u16,
+		cleary: u16,
+		seed: u64,
+	) -> Result<Self, NewBoardError> {
+		B::with_clearing_seeded(x, y, bombs, clearx, cleary, seed).map(Self::new)
+	}
+
+	/// resets the shared board, so every handle sees the new board
+	fn reset_with(&mut self, x: u16, y: u16, bombs: u32) -> Result<(), NewBoardError> {
+		self.write().reset_with(x, y, bombs)
+	}
+
+	impl_read!(dimensions, (u16, u16));
+	impl_read!(bomb_count, u32);
+	impl_read!(opened, u32);
+	impl_read!(flagged, u32);
+	impl_read!(state, GameState);
+	impl_read!(layout_3bv, Option<u32>);
+	// rendering once under one lock is both faster and consistent, unlike the default impl locking per tile
+	impl_read!(render, FlatBoard<VisibleTile>);
+	impl_read!(tile_census, TileCensus);
+	impl_read!(tiles_left, u32);
+	impl_read!(unflagged_bombs, u32);
+
+	impl_read_at!(preview_chord, Result<Vec<(u16, u16)>, UnopenableError>);
+	impl_read_at!(opened_count_at, Option<u8>);
+	impl_read_at!(remaining_mines_around, Option<MineCount>);
+	impl_read_at!(flags_around, u8);
+	impl_read_at!(unopened_around, u8);
+
+	fn render_window(&self, x0: u16, y0: u16, w: u16, h: u16) -> FlatBoard<VisibleTile> {
+		self.read().render_window(x0, y0, w, h)
+	}
+
+	/// yields the rows of a single render, so every row is from the same board
+	///
+	/// the lock is not held while the rows are consumed, so unlike the default impl the whole render is held in memory
+	fn render_rows(&self) -> impl Iterator<Item = Vec<VisibleTile>> {
+		let board = self.render();
+
+		(0..board.len()).map(move |y| board[y].to_vec())
+	}
+
+	/// collects the frontier under one lock, as the lock can not be held while the tiles are consumed
+	fn frontier(&self) -> impl Iterator<Item = (u16, u16)> {
+		self.read().frontier().collect::<Vec<_>>().into_iter()
+	}
+
+	impl_move!(open_tile);
+	impl_move!(open_around);
+	impl_move!(flag_tile);
+	// the default impls of these read the board before moving, which must happen under the same lock as the move
+	impl_move!(open_or_chord);
+
+	fn flag_or_chord(&mut self, x: u16, y: u16) -> Result<Option<GameBoardEvent>, UnopenableError> {
+		self.write().flag_or_chord(x, y)
+	}
+
+	fn toggle_flag_all_satisfied(&mut self) -> Vec<GameBoardEvent> {
+		self.write().toggle_flag_all_satisfied()
+	}
+
+	fn open_index(&mut self, idx: u32) -> Result<GameBoardEvent, UnopenableError> {
+		self.write().open_index(idx)
+	}
+
+	fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
+		self.read().get_board_tile(x, y)
+	}
+
+	fn annotation(&self, x: u16, y: u16) -> Option<char> {
+		self.read().annotation(x, y)
+	}
+
+	fn chord_policy(&self) -> ChordPolicy {
+		self.read().chord_policy()
+	}
+
+	fn snapshot(&self) -> BoardSnapshot {
+		self.read().snapshot()
+	}
+
+	fn restore(&mut self, snapshot: &BoardSnapshot) -> Result<(), RestoreError> {
+		self.write().restore(snapshot)
+	}
+
+	fn undo_move(&mut self, event: &GameBoardEvent) -> Result<(), UndoError> {
+		self.write().undo_move(event)
+	}
+
+	fn lose_game(&mut self) {
+		self.write().lose_game();
+	}
+
+	fn win_game(&mut self) -> Result<(), u32> {
+		self.write().win_game()
+	}
+
+	fn do_event(&mut self, k: KeyEvent) -> Result<(), UnopenableError> {
+		self.write().do_event(k)
+	}
+
+	// losing on a bomb hit happens under the same lock as the move
+	fn do_event_outcome(&mut self, k: KeyEvent) -> Result<EventOutcome, UnopenableError> {
+		self.write().do_event_outcome(k)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::thread;
+
+	use super::*;
+	use crate::gameboard::GameBoard;
+
+	#[test]
+	fn moves_on_another_thread_are_seen_by_every_handle() {
+		let board =
+			SyncGameBoard::new(GameBoard::with_clearing_seeded(30, 16, 99, 15, 8, 4).unwrap());
+		let mut solver = board.clone();
+
+		let moves = thread::spawn(move || {
+			solver.open_or_chord(15, 8).unwrap();
+
+			// only forced flags are placed, so chording never hits a bomb
+			for _ in 0..4 {
+				solver.toggle_flag_all_satisfied();
+
+				for y in solver.y_range() {
+					for x in solver.x_range() {
+						if let Some(VisibleTile::Visible(_)) = solver.get_board_tile(x, y) {
+							let _ = solver.open_or_chord(x, y);
+						}
+					}
+				}
+			}
+		});
+
+		let opened = |board: &SyncGameBoard<GameBoard>| {
+			board
+				.render()
+				.iter()
+				.flatten()
+				.filter(|t| matches!(t, VisibleTile::Visible(_)))
+				.count()
+		};
+
+		// nothing is ever closed, so renders taken while the other thread moves only ever see more opened tiles
+		let mut seen = 0;
+		while !moves.is_finished() {
+			let now = opened(&board);
+			assert!(now >= seen);
+			seen = now;
+		}
+
+		moves.join().unwrap();
+
+		assert_eq!(u32::try_from(opened(&board)).unwrap(), board.opened());
+		assert!(board.opened() > 0 && !board.is_lost());
+	}
+
+	#[test]
+	fn multi_tile_reads_match_the_wrapped_board() {
+		let mut inner = GameBoard::from_board_format("100\n000\n001").unwrap();
+		inner.open_tile(2, 0).unwrap();
+		inner.flag_tile(0, 0).unwrap();
+
+		let board = SyncGameBoard::new(inner.clone());
+
+		assert_eq!(
+			board.preview_chord(1, 0).unwrap(),
+			inner.preview_chord(1, 0).unwrap()
+		);
+		assert_eq!(board.tile_census(), inner.tile_census());
+		assert_eq!(board.tiles_left(), inner.tiles_left());
+		assert_eq!(board.unflagged_bombs(), inner.unflagged_bombs());
+		assert_eq!(
+			board.remaining_mines_around(1, 1),
+			inner.remaining_mines_around(1, 1)
+		);
+		assert_eq!(board.flags_around(1, 1), inner.flags_around(1, 1));
+		assert_eq!(board.unopened_around(1, 1), inner.unopened_around(1, 1));
+		assert_eq!(
+			board.render_window(1, 1, 2, 2),
+			inner.render_window(1, 1, 2, 2)
+		);
+		assert!(board.render_rows().eq(inner.render_rows()));
+		assert!(board.frontier().eq(inner.frontier()));
+	}
+}
@@ -1,36 +1,400 @@
 //! main ui interactions, houses rendering for game view and integrations with cursive
 //!
-//! Defines the [MineGameView], an implementor of [View] for a cursive user interface
+//! Defines the [`MineGameView`], an implementor of [View] for a cursive user interface
 
 use crate::gameboard;
 use gameboard::{
-	BaseGameBoard, GameBoard, KeyEvent, NewBoardError, Tile, UnopenableError, VisibleTile,
+	narrow_xy, BaseGameBoard, Difficulty, GameBoard, GameState, Glyphs, KeyEvent, MineCount,
+	NewBoardError, Tile, VisibleTile,
 };
 
+use std::time::{Duration, Instant};
+
 use cursive::{
+	direction::Direction,
 	event,
-	theme::{BaseColor, Color, ColorStyle, ColorType},
-	view::View,
+	theme::{BaseColor, Color, ColorStyle, ColorType, Effect},
+	view::{CannotFocus, View},
 	Printer, XY,
 };
 
 use crate::lazy::LazyGameBoard;
+use crate::solver::{all_safe_tiles, Solver, DEFAULT_MAX_ENUMERATION};
+
+/// the refresh rate a [`MineGameView`] asks for while a game is in progress
+pub const DEFAULT_PLAYING_FPS: u32 = 30;
+
+/// how long a hint requested with `h` stays highlighted
+pub const HINT_DURATION: Duration = Duration::from_secs(2);
+
+/// how much of the terminal's color and unicode support a [`MineGameView`] uses
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum RenderMode {
+	/// unicode glyphs and light and dark colors
+	Full,
+	/// ascii glyphs and only the 8 dark colors, with covered tiles drawn as text
+	Basic,
+	/// ascii glyphs and the terminal's default colors, following the `NO_COLOR` convention
+	NoColor,
+}
+
+impl RenderMode {
+	/// detects the render mode from the environment
+	///
+	/// a non empty `NO_COLOR` disables colors, and a `dumb`, `linux` or `vt*` `TERM` or a non utf-8 locale falls back to [`RenderMode::Basic`]
+	#[must_use]
+	pub fn detect() -> Self {
+		use std::env;
+
+		if env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty()) {
+			return Self::NoColor;
+		}
+
+		let term = env::var("TERM").unwrap_or_default();
+		let basic_term =
+			term.is_empty() || term == "dumb" || term == "linux" || term.starts_with("vt");
+
+		// the first locale variable that is set decides the character set
+		let utf8 = ["LC_ALL", "LC_CTYPE", "LANG"]
+			.into_iter()
+			.find_map(|k| env::var(k).ok().filter(|v| !v.is_empty()))
+			.is_some_and(|v| {
+				let v = v.to_ascii_lowercase();
+				v.contains("utf-8") || v.contains("utf8")
+			});
+
+		if basic_term || !utf8 {
+			Self::Basic
+		} else {
+			Self::Full
+		}
+	}
+
+	/// returns the glyphs that this render mode can display
+	#[must_use]
+	pub const fn glyphs(self) -> Glyphs {
+		match self {
+			Self::Full => Glyphs::DEFAULT,
+			Self::Basic | Self::NoColor => Glyphs::ASCII,
+		}
+	}
+}
 
 pub struct MineGameView<T: BaseGameBoard> {
 	board: T,
+	glyphs: Glyphs,
+	render_mode: RenderMode,
+	input_mode: InputMode,
+	/// x/y of the tile selected with the keyboard
+	cursor: (u16, u16),
+	/// x/y of the tile under a held left click, which is only opened if the click is released over it
+	pressed: Option<(u16, u16)>,
+	/// whether opened numbers show the flags they still need instead of their count
+	show_remaining: bool,
+	playing_fps: u32,
+	/// when the first click of the game was made
+	started: Option<Instant>,
+	/// every left and right click made on the board
+	clicks: u32,
+	on_win: Option<WinCallback>,
+	on_lose: Option<LoseCallback>,
+	/// the last requested hint, which is only drawn for [`HINT_DURATION`]
+	hint: Option<Hint>,
+}
+
+/// a tile suggested by [`MineGameView::request_hint`]
+#[derive(Copy, Clone, Debug)]
+struct Hint {
+	tile: (u16, u16),
+	/// whether the tile is proven safe, or only the least likely to be a bomb
+	safe: bool,
+	shown: Instant,
+}
+
+/// a callback set by [`MineGameView::on_win`]
+type WinCallback = Box<dyn FnMut(&GameStats)>;
+
+/// a callback set by [`MineGameView::on_lose`]
+type LoseCallback = Box<dyn FnMut()>;
+
+/// stats of a won game, passed to the [`MineGameView::on_win`] callback
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct GameStats {
+	/// the time from the first click to the winning click
+	pub elapsed: Duration,
+	/// every left and right click made on the board, including ones that had no effect
+	pub clicks: u32,
+	/// the 3BV of the board, if the board can compute it
+	pub bv3: Option<u32>,
+}
+
+impl GameStats {
+	/// returns the 3BV per click, where 1 is a perfect game without flags
+	#[must_use]
+	pub fn efficiency(&self) -> Option<f64> {
+		match self.clicks {
+			0 => None,
+			clicks => Some(f64::from(self.bv3?) / f64::from(clicks)),
+		}
+	}
+}
+
+/// how tiles are selected in a [`MineGameView`]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum InputMode {
+	/// tiles are opened with left click and flagged with right click
+	#[default]
+	Mouse,
+	/// like [`InputMode::Mouse`], but a cursor is also drawn that is moved with the arrow keys, opens with enter or space, flags with f and asks for a hint with h
+	Keyboard,
+}
+
+/// a builder for a [`MineGameView`] over a [`LazyGameBoard`]
+#[derive(Copy, Clone, Debug)]
+pub struct MineGameViewBuilder {
+	difficulty: Difficulty,
+	render_mode: Option<RenderMode>,
+	glyphs: Option<Glyphs>,
+	input_mode: InputMode,
+	defer_first_click: bool,
+	show_remaining: bool,
+	playing_fps: u32,
+}
+
+impl Default for MineGameViewBuilder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl MineGameViewBuilder {
+	/// creates a builder for an intermediate game with a safe first click and a detected render mode
+	#[must_use]
+	pub const fn new() -> Self {
+		Self {
+			difficulty: Difficulty::Intermediate,
+			render_mode: None,
+			glyphs: None,
+			input_mode: InputMode::Mouse,
+			defer_first_click: true,
+			show_remaining: false,
+			playing_fps: DEFAULT_PLAYING_FPS,
+		}
+	}
+
+	/// sets the board size and bomb count of the game
+	#[must_use]
+	pub const fn difficulty(mut self, difficulty: Difficulty) -> Self {
+		self.difficulty = difficulty;
+		self
+	}
+
+	/// sets the render mode instead of detecting it from the environment
+	#[must_use]
+	pub const fn render_mode(mut self, render_mode: RenderMode) -> Self {
+		self.render_mode = Some(render_mode);
+		self
+	}
+
+	/// sets the glyphs instead of using the glyphs of the render mode
+	#[must_use]
+	pub const fn glyphs(mut self, glyphs: Glyphs) -> Self {
+		self.glyphs = Some(glyphs);
+		self
+	}
+
+	#[must_use]
+	pub const fn input_mode(mut self, input_mode: InputMode) -> Self {
+		self.input_mode = input_mode;
+		self
+	}
+
+	/// when true (the default), the board is generated on the first click with a clearing around it
+	///
+	/// when false, the board is generated by [`MineGameViewBuilder::build`] with the clearing around the center tile already opened
+	#[must_use]
+	pub const fn defer_first_click(mut self, defer: bool) -> Self {
+		self.defer_first_click = defer;
+		self
+	}
+
+	/// when true, opened numbers show how many more flags they need instead of their count, and are dimmed once satisfied
+	#[must_use]
+	pub const fn show_remaining(mut self, show: bool) -> Self {
+		self.show_remaining = show;
+		self
+	}
+
+	/// sets the refresh rate returned by [`MineGameView::desired_fps`] while a game is in progress
+	#[must_use]
+	pub const fn playing_fps(mut self, fps: u32) -> Self {
+		self.playing_fps = fps;
+		self
+	}
+
+	pub fn build<T: BaseGameBoard>(self) -> Result<MineGameView<LazyGameBoard<T>>, NewBoardError> {
+		let (x, y, bombs) = self.difficulty.config();
+
+		let board = if self.defer_first_click {
+			GameBoard::validate_board(x, y, bombs, None)?;
+			LazyGameBoard::new_uninit(x, y, bombs)?
+		} else {
+			GameBoard::validate_board(x, y, bombs, (x / 2, y / 2))?;
+			let mut board = LazyGameBoard::with_clearing(x, y, bombs, x / 2, y / 2)?;
+
+			// boards that do not open their clearing on creation are opened here
+			if board.get_board_tile(x / 2, y / 2) == Some(VisibleTile::NotVisible) {
+				board
+					.open_tile(x / 2, y / 2)
+					.map_err(|_| NewBoardError::InitialOpenFailed)?;
+			}
+
+			board
+		};
+
+		let render_mode = self.render_mode.unwrap_or_else(RenderMode::detect);
+
+		Ok(MineGameView {
+			board,
+			glyphs: self.glyphs.unwrap_or_else(|| render_mode.glyphs()),
+			render_mode,
+			input_mode: self.input_mode,
+			cursor: (0, 0),
+			pressed: None,
+			show_remaining: self.show_remaining,
+			playing_fps: self.playing_fps,
+			started: None,
+			clicks: 0,
+			on_win: None,
+			on_lose: None,
+			hint: None,
+		})
+	}
 }
 
 impl<T: BaseGameBoard> MineGameView<LazyGameBoard<T>> {
 	pub fn new_lazy(x: u16, y: u16, bombs: u32) -> Result<Self, NewBoardError> {
-		GameBoard::validate_board(x, y, bombs, false, None)?;
+		MineGameViewBuilder::new()
+			.difficulty(Difficulty::Custom { x, y, bombs })
+			.build()
+	}
+}
 
-		Ok(Self {
-			board: LazyGameBoard::new_uninit(x, y, bombs).unwrap(),
-		})
+impl<T: BaseGameBoard> MineGameView<T> {
+	/// overrides the detected render mode, also replacing the glyphs with the glyphs of the mode
+	#[must_use]
+	pub const fn with_render_mode(mut self, render_mode: RenderMode) -> Self {
+		self.render_mode = render_mode;
+		self.glyphs = render_mode.glyphs();
+		self
+	}
+
+	/// sets the glyphs used to draw flags and bombs
+	pub const fn set_glyphs(&mut self, glyphs: Glyphs) {
+		self.glyphs = glyphs;
+	}
+
+	/// toggles between opened numbers showing their count and the flags they still need, see [`MineGameViewBuilder::show_remaining`]
+	pub const fn set_show_remaining(&mut self, show: bool) {
+		self.show_remaining = show;
+	}
+
+	/// sets the refresh rate returned by [`MineGameView::desired_fps`] while a game is in progress
+	pub const fn set_playing_fps(&mut self, fps: u32) {
+		self.playing_fps = fps;
+	}
+
+	/// sets a callback that is called with the stats of the game when a click wins it
+	pub fn on_win(&mut self, cb: impl FnMut(&GameStats) + 'static) {
+		self.on_win = Some(Box::new(cb));
+	}
+
+	/// sets a callback that is called when a click loses the game
+	pub fn on_lose(&mut self, cb: impl FnMut() + 'static) {
+		self.on_lose = Some(Box::new(cb));
+	}
+
+	/// highlights a tile the solver proved safe for [`HINT_DURATION`], or the tile least likely to be a bomb if none is safe
+	///
+	/// nothing is highlighted once the game is over, or if the visible numbers say nothing about any tile
+	pub fn request_hint(&mut self) {
+		self.hint = None;
+
+		if self.board.state() != GameState::Playing {
+			return;
+		}
+
+		let suggestion = match all_safe_tiles(&self.board).first() {
+			Some(&tile) => Some((tile, true)),
+			None => Solver::new(&self.board)
+				.mine_probabilities(DEFAULT_MAX_ENUMERATION)
+				.probabilities
+				.into_iter()
+				.min_by(|(_, a), (_, b)| a.total_cmp(b))
+				.map(|(tile, _)| (tile, false)),
+		};
+
+		self.hint = suggestion.map(|(tile, safe)| Hint {
+			tile,
+			safe,
+			shown: Instant::now(),
+		});
+	}
+
+	/// returns the x/y of the hinted tile and whether it is proven safe, while the hint is shown
+	pub fn hinted(&self) -> Option<((u16, u16), bool)> {
+		self.hint
+			.filter(|h| h.shown.elapsed() < HINT_DURATION)
+			.map(|h| (h.tile, h.safe))
+	}
+
+	/// calls the win or lose callback if the game state changed from playing to won or lost
+	fn notify_game_over(&mut self, before: GameState) {
+		if before != GameState::Playing {
+			return;
+		}
+
+		match self.board.state() {
+			GameState::Playing => (),
+			GameState::Won => {
+				let stats = GameStats {
+					elapsed: self.started.map(|s| s.elapsed()).unwrap_or_default(),
+					clicks: self.clicks,
+					bv3: self.board.layout_3bv(),
+				};
+
+				if let Some(cb) = &mut self.on_win {
+					cb(&stats);
+				}
+			}
+			GameState::Lost => {
+				if let Some(cb) = &mut self.on_lose {
+					cb();
+				}
+			}
+		}
+	}
+
+	/// returns true while the view changes without user input, which is while a game has started and not yet ended
+	pub fn needs_refresh(&self) -> bool {
+		self.board.opened() > 0 && self.board.state() == GameState::Playing
+	}
+
+	/// returns the refresh rate the driver should run at, where 0 means only redrawing on events
+	pub fn desired_fps(&self) -> u32 {
+		if self.needs_refresh() {
+			self.playing_fps
+		} else {
+			0
+		}
 	}
 }
 
-fn visible_tile_to_cursive(v: VisibleTile) -> (ColorStyle, String) {
+fn visible_tile_to_cursive(
+	v: VisibleTile,
+	glyphs: &Glyphs,
+	mode: RenderMode,
+) -> (ColorStyle, String) {
 	macro_rules! tty_color {
 		($lightness:ident::$color:ident) => {
 			ColorType::Color(Color::$lightness(BaseColor::$color))
@@ -39,16 +403,21 @@ fn visible_tile_to_cursive(v: VisibleTile) -> (ColorStyle, String) {
 
 	const fn colorof(tile: Tile) -> ColorType {
 		match tile {
-			Tile::Zero => tty_color!(Dark::Black),
+			Tile::Zero | Tile::Seven => tty_color!(Dark::Black),
 			Tile::One => tty_color!(Light::Blue),
 			Tile::Two => tty_color!(Light::Green),
 			Tile::Three => tty_color!(Light::Red),
 			Tile::Four => tty_color!(Dark::Blue),
-			Tile::Five => tty_color!(Dark::Red),
+			Tile::Five | Tile::Bomb => tty_color!(Dark::Red),
 			Tile::Six => tty_color!(Dark::Cyan),
-			Tile::Seven => tty_color!(Dark::Black),
 			Tile::Eight => tty_color!(Light::Black),
-			Tile::Bomb => tty_color!(Dark::Red),
+		}
+	}
+
+	const fn basic(c: ColorType) -> ColorType {
+		match c {
+			ColorType::Color(Color::Light(base)) => ColorType::Color(Color::Dark(base)),
+			other => other,
 		}
 	}
 
@@ -61,106 +430,632 @@ fn visible_tile_to_cursive(v: VisibleTile) -> (ColorStyle, String) {
 		back: covered_tile_backing,
 	};
 
-	const FLAG_CHAR: &str = "\u{2691} ";
-
-	match v {
+	let (style, text) = match v {
 		VisibleTile::Visible(tile) => (
 			ColorStyle {
 				front: colorof(tile),
 				back: uncovered_tile_backing,
 			},
-			format!("{} ", tile.as_str_count()),
+			format!("{} ", tile.as_glyph(glyphs)),
 		),
 		VisibleTile::NotVisible => (basic_color, String::from("  ")),
-		VisibleTile::Flagged => (basic_color, String::from(FLAG_CHAR)),
+		VisibleTile::Flagged => (basic_color, format!("{} ", glyphs.flag)),
+		VisibleTile::DetonatedBomb => (
+			ColorStyle {
+				front: black,
+				back: tty_color!(Dark::Red),
+			},
+			format!("{} ", glyphs.mine_hit),
+		),
+		VisibleTile::WrongFlag => (
+			ColorStyle {
+				front: tty_color!(Dark::Red),
+				back: covered_tile_backing,
+			},
+			format!("{} ", glyphs.wrong_flag),
+		),
+	};
+
+	// without light colors covered and opened tiles share a backing, so covered tiles need text
+	let text = if mode != RenderMode::Full && v == VisibleTile::NotVisible {
+		String::from("##")
+	} else {
+		text
+	};
+
+	match mode {
+		RenderMode::Full => (style, text),
+		RenderMode::Basic => (
+			ColorStyle {
+				front: basic(style.front),
+				back: basic(style.back),
+			},
+			text,
+		),
+		RenderMode::NoColor => (ColorStyle::terminal_default(), text),
 	}
 }
 
+/// maps a mouse position on screen to the x/y of the board tile under it
+///
+/// every tile is 2 columns wide and the first row is the header, returns [`None`] for positions left of or above the board
+fn screen_to_board(position: XY<usize>, offset: XY<usize>) -> Option<(u16, u16)> {
+	let rel_x = position.x.checked_sub(offset.x)?;
+	let rel_y = position.y.checked_sub(offset.y)?.checked_sub(1)?;
+
+	Some(((rel_x / 2).try_into().ok()?, rel_y.try_into().ok()?))
+}
+
+/// the smallest size a [`MineGameView`] is usable at, fitting the bomb counter and one tile
+pub const MIN_VIEW_SIZE: XY<usize> = XY { x: 2, y: 2 };
+
 impl<B: BaseGameBoard + 'static> View for MineGameView<B> {
 	fn draw(&self, p: &Printer<'_, '_>) {
 		p.print((0usize, 0), format!("{}", self.board.bomb_count()).as_str());
 
 		let base_render = self.board.render();
 
-		for (y_idx, y) in base_render.iter().enumerate() {
-			for (x_idx, x) in y.iter().enumerate() {
-				let (style, string) = visible_tile_to_cursive(*x);
+		let cursor = match self.input_mode {
+			InputMode::Keyboard => Some(self.cursor),
+			InputMode::Mouse => None,
+		};
 
-				p.with_color(style, |colored_print| {
+		let hinted = self.hinted();
+		let is_hinted = |x_idx: usize, y_idx: usize| {
+			hinted.is_some_and(|((hx, hy), _)| usize::from(hx) == x_idx && usize::from(hy) == y_idx)
+		};
+
+		// the keyboard cursor and the tile under a held left click are drawn reversed, and so is a hint without colors
+		let highlighted = |x_idx: usize, y_idx: usize| {
+			[cursor, self.pressed]
+				.into_iter()
+				.flatten()
+				.any(|(cx, cy)| usize::from(cx) == x_idx && usize::from(cy) == y_idx)
+				|| (self.render_mode == RenderMode::NoColor && is_hinted(x_idx, y_idx))
+		};
+
+		// only draw the tiles that fit the printer, the first row is taken by the bomb counter
+		let rows = p.size.y.saturating_sub(1);
+		let columns = p.size.x / 2;
+
+		for (y_idx, y) in base_render.iter().enumerate().take(rows) {
+			for (x_idx, x) in y.iter().enumerate().take(columns) {
+				let (tile_x, tile_y) = narrow_xy(x_idx, y_idx);
+				let (mut style, mut string) =
+					visible_tile_to_cursive(*x, &self.glyphs, self.render_mode);
+
+				// a safe hint is backed green, and a guess is backed yellow
+				if let Some((_, safe)) = hinted.filter(|_| is_hinted(x_idx, y_idx)) {
+					if self.render_mode != RenderMode::NoColor {
+						style.back = ColorType::Color(Color::Dark(if safe {
+							BaseColor::Green
+						} else {
+							BaseColor::Yellow
+						}));
+					}
+				}
+
+				let remaining = match x {
+					VisibleTile::Visible(t) if self.show_remaining && t.as_count() > Some(0) => {
+						self.board.remaining_mines_around(tile_x, tile_y)
+					}
+					_ => None,
+				};
+
+				if let Some(remaining) = remaining {
+					string = format!("{remaining} ");
+				}
+
+				let annotation = match x {
+					VisibleTile::NotVisible => self.board.annotation(tile_x, tile_y),
+					_ => None,
+				};
+
+				if let Some(mark) = annotation {
+					string = format!("{mark} ");
+				}
+
+				let print = |colored_print: &Printer<'_, '_>| {
 					colored_print.print((x_idx * 2, y_idx + 1), string.as_str());
+				};
+
+				// satisfied numbers are dimmed while showing remaining flags, and annotations are always faint
+				let print_dimmed = |colored_print: &Printer<'_, '_>| {
+					if remaining == Some(MineCount::ZERO) || annotation.is_some() {
+						colored_print.with_effect(Effect::Dim, print);
+					} else {
+						print(colored_print);
+					}
+				};
+
+				p.with_color(style, |colored_print| {
+					if highlighted(x_idx, y_idx) {
+						colored_print.with_effect(Effect::Reverse, print_dimmed);
+					} else {
+						print_dimmed(colored_print);
+					}
 				});
 			}
 		}
 	}
 
-	fn required_size(&mut self, _: XY<usize>) -> XY<usize> {
+	/// asks for the whole board, but settles for the constraint down to [`MIN_VIEW_SIZE`] on small terminals
+	fn required_size(&mut self, constraint: XY<usize>) -> XY<usize> {
 		let (x, y) = self.board.dimensions();
 
 		XY {
 			x: usize::from(x) * 2,
 			y: usize::from(y) + 1usize,
 		}
+		.zip_map(constraint, usize::min)
+		.zip_map(MIN_VIEW_SIZE, usize::max)
 	}
 
-	fn on_event(&mut self, e: event::Event) -> event::EventResult {
-		use std::io::prelude::*;
-		let mut elog = std::fs::OpenOptions::new()
-			.write(true)
-			.create(true)
-			.append(true)
-			.open("elog.txt")
-			.unwrap();
+	/// only takes focus in [`InputMode::Keyboard`], where key presses are needed
+	fn take_focus(&mut self, _: Direction) -> Result<event::EventResult, CannotFocus> {
+		match self.input_mode {
+			InputMode::Keyboard => Ok(event::EventResult::Consumed(None)),
+			InputMode::Mouse => Err(CannotFocus),
+		}
+	}
 
-		let log_err = |e| elog.write(format!("{:?} {}\n", e, e).as_bytes()).unwrap();
+	fn on_event(&mut self, e: event::Event) -> event::EventResult {
+		use event::{Event, EventResult, Key, MouseButton, MouseEvent};
 
-		use event::{Event, EventResult, MouseButton, MouseEvent};
+		let keyboard = self.input_mode == InputMode::Keyboard;
+		let (cx, cy) = self.cursor;
 
-		match e {
+		let key_event = match e {
 			Event::Mouse {
 				position,
 				event,
 				offset,
 			} => {
-				if ((position.y as isize) - (offset.y as isize)) <= 0
-					|| ((position.x as isize) - (offset.x as isize)) < 0
-				{
-					return EventResult::Ignored;
-				}
+				let board_p = screen_to_board(position, offset);
 
-				let board_p: (u16, u16) = (|| {
-					Some((
-						((position.x - offset.x) / 2).try_into().ok()?,
-						((position.y - offset.y) - 1).try_into().ok()?,
-					))
-				})()
-				.unwrap_or((u16::MAX, u16::MAX));
-
-				match event {
-					MouseEvent::Press(b) => match b {
-						MouseButton::Left => {
-							if let Err(err) =
-								self.board.do_event(KeyEvent::Mouse1(board_p.0, board_p.1))
-							{
-								match err {
-									UnopenableError::BombHit => self.board.lose_game(),
-									_ => {}
-								}
-							}
-							EventResult::Consumed(None)
+				// a left click opens on release, and moving off the pressed tile before releasing cancels it
+				if event == MouseEvent::Release(MouseButton::Left) {
+					let pressed = self.pressed.take();
+
+					match pressed.filter(|&p| Some(p) == board_p) {
+						Some((x, y)) => KeyEvent::Mouse1(x, y),
+						None if pressed.is_some() => return EventResult::Consumed(None),
+						None => return EventResult::Ignored,
+					}
+				} else {
+					let Some(board_p) = board_p else {
+						return EventResult::Ignored;
+					};
+
+					match event {
+						MouseEvent::Press(MouseButton::Left) => {
+							self.pressed = Some(board_p);
+							return EventResult::Consumed(None);
 						}
-						MouseButton::Right => {
-							let _ = self
-								.board
-								.do_event(KeyEvent::Mouse2(board_p.0, board_p.1))
-								.map_err(log_err);
-							EventResult::Consumed(None)
+						MouseEvent::Hold(MouseButton::Left) if self.pressed.is_some() => {
+							return EventResult::Consumed(None);
 						}
-						_ => EventResult::Ignored,
-					},
-					_ => EventResult::Ignored,
+						MouseEvent::Press(MouseButton::Right) => {
+							KeyEvent::Mouse2(board_p.0, board_p.1)
+						}
+						_ => return EventResult::Ignored,
+					}
 				}
 			}
-			_ => EventResult::Ignored,
+			Event::Char('h') => {
+				self.request_hint();
+				return EventResult::Consumed(None);
+			}
+			Event::Char(' ') | Event::Key(Key::Enter) if keyboard => KeyEvent::Mouse1(cx, cy),
+			Event::Char('f') if keyboard => KeyEvent::Mouse2(cx, cy),
+			Event::Key(k) if keyboard => {
+				let (max_x, max_y) = (self.board.get_x() - 1, self.board.get_y() - 1);
+
+				self.cursor = match k {
+					Key::Left => (cx.saturating_sub(1), cy),
+					Key::Right => (cx.saturating_add(1).min(max_x), cy),
+					Key::Up => (cx, cy.saturating_sub(1)),
+					Key::Down => (cx, cy.saturating_add(1).min(max_y)),
+					_ => return EventResult::Ignored,
+				};
+
+				return EventResult::Consumed(None);
+			}
+			_ => return EventResult::Ignored,
+		};
+
+		let before = self.board.state();
+
+		// any move makes the hint outdated
+		self.hint = None;
+
+		if before == GameState::Playing {
+			self.started.get_or_insert_with(Instant::now);
+			self.clicks += 1;
+		}
+
+		match key_event {
+			KeyEvent::Mouse1(..) | KeyEvent::Mouse2(..) => {
+				// hitting a bomb, which a right click chord can do too, is handled by do_event_outcome ending the game
+				let _ = self.board.do_event_outcome(key_event);
+			}
+			_ => {
+				let _ = self.board.do_event(key_event);
+			}
+		}
+
+		self.notify_game_over(before);
+
+		EventResult::Consumed(None)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use cursive::backend::Backend as _;
+	use cursive::backends::puppet::{observed::ObservedScreen, Backend};
+
+	use super::*;
+	use crate::gameboard::{FlatBoard, GameBoardEvent, UndoError, UnopenableError};
+	use crate::mock::{MockCall, MockGameBoard};
+
+	const OFFSET: XY<usize> = XY { x: 3, y: 2 };
+
+	/// builds a keyboard view over a lazy 10x10 board with 50 bombs, which no single click can win
+	fn lazy_view() -> MineGameView<LazyGameBoard<GameBoard>> {
+		MineGameViewBuilder::new()
+			.difficulty(Difficulty::Custom {
+				x: 10,
+				y: 10,
+				bombs: 50,
+			})
+			.render_mode(RenderMode::NoColor)
+			.input_mode(InputMode::Keyboard)
+			.build()
+			.unwrap()
+	}
+
+	fn at(x: usize, y: usize) -> XY<usize> {
+		XY { x, y }
+	}
+
+	#[test]
+	fn screen_to_board_maps_both_columns_of_a_tile() {
+		// the first row under the header, both halves of the first and of the tenth column
+		assert_eq!(screen_to_board(at(3, 3), OFFSET), Some((0, 0)));
+		assert_eq!(screen_to_board(at(4, 3), OFFSET), Some((0, 0)));
+		assert_eq!(screen_to_board(at(21, 3), OFFSET), Some((9, 0)));
+		assert_eq!(screen_to_board(at(22, 3), OFFSET), Some((9, 0)));
+		assert_eq!(screen_to_board(at(23, 3), OFFSET), Some((10, 0)));
+		assert_eq!(screen_to_board(at(3, 12), OFFSET), Some((0, 9)));
+	}
+
+	#[test]
+	fn screen_to_board_rejects_the_header_and_left_margin() {
+		assert_eq!(screen_to_board(at(3, 2), OFFSET), None);
+		assert_eq!(screen_to_board(at(2, 3), OFFSET), None);
+		assert_eq!(screen_to_board(at(0, 0), OFFSET), None);
+	}
+
+	#[test]
+	fn refreshes_only_while_playing() {
+		let mut view = lazy_view();
+		view.set_playing_fps(60);
+
+		assert!(!view.needs_refresh());
+		assert_eq!(view.desired_fps(), 0);
+
+		view.on_event(event::Event::Char(' '));
+		assert_eq!(view.desired_fps(), 60);
+
+		view.board.lose_game();
+		assert_eq!(view.desired_fps(), 0);
+	}
+
+	#[test]
+	fn render_modes_downgrade_colors_and_covered_tiles() {
+		let draw = |mode: RenderMode, tile| visible_tile_to_cursive(tile, &mode.glyphs(), mode);
+
+		let (full, text) = draw(RenderMode::Full, VisibleTile::NotVisible);
+		assert_eq!(text, "  ");
+		assert_eq!(full.back, ColorType::Color(Color::Light(BaseColor::White)));
+
+		let (basic, text) = draw(RenderMode::Basic, VisibleTile::NotVisible);
+		assert_eq!(text, "##");
+		assert_eq!(basic.back, ColorType::Color(Color::Dark(BaseColor::White)));
+
+		let (plain, text) = draw(RenderMode::NoColor, VisibleTile::Flagged);
+		assert_eq!(text, "F ");
+		assert_eq!(plain, ColorStyle::terminal_default());
+	}
+
+	#[test]
+	fn builder_render_mode_overrides_detection() {
+		assert_eq!(lazy_view().render_mode, RenderMode::NoColor);
+	}
+
+	#[test]
+	fn builder_validates_and_applies_options() {
+		let custom = |x, y, bombs| Difficulty::Custom { x, y, bombs };
+
+		let overflow = MineGameViewBuilder::new()
+			.difficulty(custom(3, 3, 10))
+			.build::<GameBoard>();
+		assert!(matches!(overflow, Err(NewBoardError::BombOverflow)));
+
+		// an immediate board needs room for a clearing, a deferred one does not
+		let cramped = MineGameViewBuilder::new().difficulty(custom(3, 3, 4));
+		assert!(cramped.build::<GameBoard>().is_ok());
+		assert!(matches!(
+			cramped.defer_first_click(false).build::<GameBoard>(),
+			Err(NewBoardError::BombOverflow)
+		));
+
+		let view = MineGameViewBuilder::new()
+			.difficulty(custom(9, 9, 10))
+			.defer_first_click(false)
+			.render_mode(RenderMode::Basic)
+			.glyphs(Glyphs::DEFAULT)
+			.input_mode(InputMode::Keyboard)
+			.build::<GameBoard>()
+			.unwrap();
+
+		assert!(view.board.opened() > 0);
+		assert_eq!(view.glyphs, Glyphs::DEFAULT);
+		assert_eq!(view.input_mode, InputMode::Keyboard);
+	}
+
+	/// wraps any board in a mouse view with ascii glyphs and no colors
+	fn view<B: BaseGameBoard>(board: B) -> MineGameView<B> {
+		MineGameView {
+			board,
+			glyphs: Glyphs::ASCII,
+			render_mode: RenderMode::NoColor,
+			input_mode: InputMode::Mouse,
+			cursor: (0, 0),
+			pressed: None,
+			show_remaining: false,
+			playing_fps: DEFAULT_PLAYING_FPS,
+			started: None,
+			clicks: 0,
+			on_win: None,
+			on_lose: None,
+			hint: None,
+		}
+	}
+
+	/// draws view on a puppet screen of the given size, which panics on any print outside of it
+	fn drawn<B: BaseGameBoard + 'static>(
+		view: &MineGameView<B>,
+		size: XY<usize>,
+	) -> ObservedScreen {
+		let mut backend = Backend::init(Some(size));
+		let theme = cursive::theme::Theme::default();
+
+		view.draw(&Printer::new(size, &theme, &*backend));
+		backend.refresh();
+
+		backend.stream().try_iter().last().unwrap()
+	}
+
+	#[test]
+	fn draw_clips_to_a_printer_smaller_than_the_board() {
+		let board = FlatBoard::new(9, 9, VisibleTile::Flagged);
+		let mut view = view(MockGameBoard::new(board, 10));
+
+		let size = XY { x: 5, y: 3 };
+		assert_eq!(view.required_size(size), size);
+		assert_eq!(view.required_size(XY { x: 0, y: 0 }), MIN_VIEW_SIZE);
+
+		let screen = drawn(&view, size);
+		assert_eq!(screen.find_occurences("F F").len(), 2);
+		assert!(screen.find_occurences("F F F").is_empty());
+	}
+
+	#[test]
+	fn releasing_off_the_pressed_tile_cancels_the_click() {
+		use cursive::event::{Event, MouseButton, MouseEvent};
+
+		let mouse = |x, event| Event::Mouse {
+			offset: OFFSET,
+			position: at(x, 3),
+			event,
+		};
+
+		let board = FlatBoard::new(3, 3, VisibleTile::NotVisible);
+		let mut view = view(MockGameBoard::new(board, 1));
+
+		view.on_event(mouse(3, MouseEvent::Press(MouseButton::Left)));
+		assert_eq!(view.pressed, Some((0, 0)));
+		view.on_event(mouse(5, MouseEvent::Release(MouseButton::Left)));
+
+		assert_eq!(view.pressed, None);
+		assert!(view.board.calls().is_empty());
+
+		view.on_event(mouse(3, MouseEvent::Press(MouseButton::Left)));
+		view.on_event(mouse(4, MouseEvent::Release(MouseButton::Left)));
+		assert_eq!(view.board.calls(), [MockCall::OpenTile(0, 0)]);
+	}
+
+	#[test]
+	fn show_remaining_draws_the_flags_a_number_still_needs() {
+		let mut gb = GameBoard::from_board_format("101\n000\n000").unwrap();
+		gb.open_tile(1, 2).unwrap();
+		gb.flag_tile(0, 0).unwrap();
+
+		let mut view = view(gb);
+		let size = XY { x: 6, y: 4 };
+
+		// the row under the bombs reads 1 2 1
+		assert_eq!(drawn(&view, size).find_occurences("1 2 1").len(), 1);
+
+		view.set_show_remaining(true);
+		assert_eq!(drawn(&view, size).find_occurences("0 1 1").len(), 1);
+	}
+
+	#[test]
+	fn game_over_callbacks_fire_once_on_the_ending_click() {
+		use std::cell::{Cell, RefCell};
+		use std::rc::Rc;
+
+		use cursive::event::{Event, MouseButton, MouseEvent};
+
+		let click = |view: &mut MineGameView<GameBoard>, x: usize, y: usize| {
+			for event in [
+				MouseEvent::Press(MouseButton::Left),
+				MouseEvent::Release(MouseButton::Left),
+			] {
+				view.on_event(Event::Mouse {
+					offset: OFFSET,
+					position: at(3 + 2 * x, 3 + y),
+					event,
+				});
+			}
+		};
+
+		let layout = || GameBoard::from_board_format("100\n000\n000").unwrap();
+
+		let wins = Rc::new(RefCell::new(vec![]));
+		let mut won = view(layout());
+		let seen = Rc::clone(&wins);
+		won.on_win(move |stats| seen.borrow_mut().push(*stats));
+		won.on_lose(|| panic!("a won game must not call the lose callback"));
+
+		click(&mut won, 2, 2);
+		click(&mut won, 2, 2);
+		click(&mut won, 0, 0);
+
+		let wins = wins.borrow();
+		assert_eq!(wins.len(), 1);
+		assert_eq!(wins[0].bv3, Some(1));
+		assert_eq!(wins[0].clicks, 1);
+		assert_eq!(wins[0].efficiency(), Some(1.0));
+
+		let losses = Rc::new(Cell::new(0));
+		let mut lost = view(layout());
+		let seen = Rc::clone(&losses);
+		lost.on_lose(move || seen.set(seen.get() + 1));
+		lost.on_win(|_| panic!("a lost game must not call the win callback"));
+
+		click(&mut lost, 0, 0);
+		click(&mut lost, 2, 2);
+
+		assert_eq!(losses.get(), 1);
+	}
+
+	#[test]
+	fn annotations_are_drawn_on_hidden_tiles_only() {
+		let mut board = GameBoard::from_board_format("100\n000\n000").unwrap();
+		board.options_mut().auto_cascade = false;
+		board.open_tile(1, 0).unwrap();
+		board.annotate(0, 0, Some('?')).unwrap();
+		board.annotate(1, 0, Some('!')).unwrap();
+
+		let screen = drawn(&view(board), XY { x: 6, y: 4 });
+
+		// the opened number keeps its count under the mark
+		assert_eq!(screen.find_occurences("? 1").len(), 1);
+		assert!(screen.find_occurences("!").is_empty());
+	}
+
+	#[test]
+	fn pressing_h_hints_a_safe_tile_or_the_best_guess() {
+		use crate::gameboard::Tile;
+
+		let mut board = FlatBoard::new(3, 3, VisibleTile::NotVisible);
+		board[0][1] = VisibleTile::Visible(Tile::One);
+
+		// the 1 has a single bomb among its five unknowns, so there is nothing safe to hint yet
+		let mut guess = view(MockGameBoard::new(board.clone(), 1));
+		assert_eq!(guess.hinted(), None);
+		guess.on_event(event::Event::Char('h'));
+		let ((x, y), safe) = guess.hinted().unwrap();
+		assert!(!safe);
+		assert!(y <= 1 && x <= 2 && (x, y) != (1, 0));
+
+		// flagging its bomb proves every other tile around the 1 safe
+		board[0][0] = VisibleTile::Flagged;
+		let mut hinted = view(MockGameBoard::new(board, 1));
+		hinted.on_event(event::Event::Char('h'));
+		assert_eq!(hinted.hinted(), Some(((0, 1), true)));
+		assert!(hinted.board.calls().is_empty());
+
+		// no hint is given once the game is over
+		hinted.board.lose_game();
+		hinted.on_event(event::Event::Char('h'));
+		assert_eq!(hinted.hinted(), None);
+	}
+
+	/// a board that puts its only bomb under the clearing, for boards whose generation does not keep the clearing safe
+	struct BombUnderClearing(GameBoard);
+
+	impl BaseGameBoard for BombUnderClearing {
+		fn dimensions(&self) -> (u16, u16) {
+			self.0.dimensions()
+		}
+
+		fn bomb_count(&self) -> u32 {
+			self.0.bomb_count()
+		}
+
+		fn opened(&self) -> u32 {
+			self.0.opened()
+		}
+
+		fn flagged(&self) -> u32 {
+			self.0.flagged()
+		}
+
+		fn with_clearing(
+			x: u16,
+			y: u16,
+			_: u32,
+			clearx: u16,
+			cleary: u16,
+		) -> Result<Self, NewBoardError> {
+			GameBoard::with_bombs(x, y, &[(clearx, cleary)]).map(Self)
+		}
+
+		fn open_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+			self.0.open_tile(x, y)
 		}
+
+		fn open_around(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+			self.0.open_around(x, y)
+		}
+
+		fn flag_tile(&mut self, x: u16, y: u16) -> Result<GameBoardEvent, UnopenableError> {
+			self.0.flag_tile(x, y)
+		}
+
+		fn undo_move(&mut self, event: &GameBoardEvent) -> Result<(), UndoError> {
+			self.0.undo_move(event)
+		}
+
+		fn get_board_tile(&self, x: u16, y: u16) -> Option<VisibleTile> {
+			self.0.get_board_tile(x, y)
+		}
+
+		fn lose_game(&mut self) {
+			self.0.lose_game();
+		}
+
+		fn win_game(&mut self) -> Result<(), u32> {
+			self.0.win_game()
+		}
+
+		fn state(&self) -> GameState {
+			self.0.state()
+		}
+	}
+
+	#[test]
+	fn builder_reports_a_failed_opening_instead_of_panicking() {
+		let built = MineGameViewBuilder::new()
+			.difficulty(Difficulty::Beginner)
+			.defer_first_click(false)
+			.build::<BombUnderClearing>();
+
+		assert!(matches!(built, Err(NewBoardError::InitialOpenFailed)));
 	}
 }